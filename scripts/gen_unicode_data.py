@@ -0,0 +1,75 @@
+#!/usr/bin/env python3
+# Generates src/unicode_data.rs from the Unicode character database built into Python's unicodedata module
+# Run from the repository root: python3 scripts/gen_unicode_data.py
+# The table is from Unicode 14.0.0 (as in Python 3.11), so another version of the database is refused
+
+import re
+import sys
+import unicodedata
+
+UCD_VERSION = "14.0.0"
+OUTPUT = "src/unicode_data.rs"
+
+# the blocks whose names are kept: Latin, Greek, Cyrillic, punctuation, currency, arrows, mathematical operators,
+# shapes, dingbats, specials and emoji; characters outside them are written with name=<not in table>
+NAMED_RANGES = [
+	(0x0000, 0x024F), (0x0300, 0x04FF), (0x2000, 0x206F), (0x20A0, 0x20CF), (0x2100, 0x22FF), (0x2500, 0x27BF),
+	(0x3000, 0x303F), (0xFE00, 0xFE0F), (0xFEFF, 0xFEFF), (0xFFF9, 0xFFFD), (0x1F300, 0x1F64F), (0x1F680, 0x1F6FF),
+	(0x1F900, 0x1F9FF)
+]
+
+
+def numbered_prefix(code_point):
+	"""Gets the prefix of a name made of a prefix and the code point in hexadecimal, such as CJK UNIFIED IDEOGRAPH-4E00"""
+	match = re.match(r"^(.*)-%04X$" % code_point, unicodedata.name(chr(code_point), ""))
+	return match.group(1) if match else None
+
+
+def main():
+	if unicodedata.unidata_version != UCD_VERSION:
+		sys.exit("Python has Unicode %s but the table is from %s" % (unicodedata.unidata_version, UCD_VERSION))
+
+	out = ["// Generated by scripts/gen_unicode_data.py from the Unicode %s character database; do not edit by hand" % UCD_VERSION, ""]
+
+	out.append("/// The general category of every code point from each start code point up to the next")
+	out.append("pub const CATEGORIES: &[(u32, &str)] = &[")
+	categories = []
+	for code_point in range(0x110000):
+		category = unicodedata.category(chr(code_point))
+		if not categories or categories[-1][1] != category:
+			categories.append((code_point, category))
+	for i in range(0, len(categories), 8):
+		out.append("\t" + " ".join("(0x%04X, \"%s\")," % run for run in categories[i:i + 8]))
+	out += ["];", ""]
+
+	out.append("/// Ranges of code points named by a prefix and their code point in hexadecimal")
+	out.append("pub const NUMBERED_NAMES: &[(u32, u32, &str)] = &[")
+	ranges = []
+	for code_point in range(0x110000):
+		prefix = numbered_prefix(code_point)
+		if prefix is None:
+			continue
+		if ranges and ranges[-1][2] == prefix and ranges[-1][1] == code_point - 1:
+			ranges[-1][1] = code_point
+		else:
+			ranges.append([code_point, code_point, prefix])
+	for first, last, prefix in ranges:
+		out.append("\t(0x%04X, 0x%04X, \"%s\")," % (first, last, prefix))
+	out += ["];", ""]
+
+	out.append("/// The names of the code points in the blocks that come up most often: Latin, Greek, Cyrillic, punctuation,")
+	out.append("/// currency, arrows, mathematical operators, shapes, dingbats, specials and emoji")
+	out.append("pub const NAMES: &[(u32, &str)] = &[")
+	for first, last in NAMED_RANGES:
+		for code_point in range(first, last + 1):
+			name = unicodedata.name(chr(code_point), "")
+			if name and numbered_prefix(code_point) is None:
+				out.append("\t(0x%04X, \"%s\")," % (code_point, name))
+	out.append("];")
+
+	with open(OUTPUT, "w") as file:
+		file.write("\n".join(out) + "\n")
+
+
+if __name__ == "__main__":
+	main()
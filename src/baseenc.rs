@@ -0,0 +1,264 @@
+use bitvec::prelude::BitVec;
+
+use crate::bytes::{bits_of, bits_to_bytes, bytes_to_bits};
+
+/// The alphabet of a text encoding of bytes or numbers
+#[derive(PartialEq, Eq, Debug)]
+#[derive(Clone, Copy)]
+pub enum Alphabet {
+	/// Bitcoin's base 58, which writes each leading zero byte as a '1'
+	Base58,
+	/// RFC 4648 base 32 of the bytes, A-Z then 2-7
+	Base32,
+	/// RFC 4648 base 32 of the bytes with the extended hex alphabet, 0-9 then A-V
+	Base32Hex,
+	/// Crockford's base 32 of the number, 0-9 then A-Z without I, L, O and U
+	Crockford,
+	/// RFC 4648 base 64 of the bytes
+	Base64,
+	/// RFC 4648 base 64 of the bytes with '-' and '_' in place of '+' and '/'
+	Base64Url
+}
+
+/// A text encoding of a value, e.g. 'base64url:nopad'
+#[derive(PartialEq, Eq, Debug)]
+#[derive(Clone, Copy)]
+pub struct BaseEncoding {
+	pub alphabet: Alphabet,
+	/// writes '=' up to a whole group of characters
+	pub pad: bool,
+	/// keeps leading zero bytes up to the width of the value instead of writing the smallest number of bytes
+	pub zeros: bool
+}
+
+impl BaseEncoding {
+	pub fn parse(param: &str) -> Result<BaseEncoding, String> {
+		let mut parts = param.split(':');
+		let alphabet = match parts.next().unwrap() {
+			"base58" | "b58" => Alphabet::Base58,
+			"base32" | "b32" => Alphabet::Base32,
+			"base32hex" | "b32hex" => Alphabet::Base32Hex,
+			"crockford" => Alphabet::Crockford,
+			"base64" | "b64" => Alphabet::Base64,
+			"base64url" | "b64url" => Alphabet::Base64Url,
+			name => return Err(format!("Unknown encoding: {}", name))
+		};
+		let mut encoding = BaseEncoding { alphabet, pad: matches!(alphabet, Alphabet::Base32 | Alphabet::Base32Hex | Alphabet::Base64), zeros: false };
+		for option in parts {
+			match option {
+				"pad" if encoding.group_chars().is_some() => encoding.pad = true,
+				"nopad" => encoding.pad = false,
+				"zeros" => encoding.zeros = true,
+				_ => return Err(format!("Unknown option {} for encoding {}", option, param))
+			}
+		}
+		Ok(encoding)
+	}
+
+	fn chars(&self) -> &'static [u8] {
+		match self.alphabet {
+			Alphabet::Base58 => b"123456789ABCDEFGHJKLMNPQRSTUVWXYZabcdefghijkmnopqrstuvwxyz",
+			Alphabet::Base32 => b"ABCDEFGHIJKLMNOPQRSTUVWXYZ234567",
+			Alphabet::Base32Hex => b"0123456789ABCDEFGHIJKLMNOPQRSTUV",
+			Alphabet::Crockford => b"0123456789ABCDEFGHJKMNPQRSTVWXYZ",
+			Alphabet::Base64 => b"ABCDEFGHIJKLMNOPQRSTUVWXYZabcdefghijklmnopqrstuvwxyz0123456789+/",
+			Alphabet::Base64Url => b"ABCDEFGHIJKLMNOPQRSTUVWXYZabcdefghijklmnopqrstuvwxyz0123456789-_"
+		}
+	}
+
+	/// The number of characters in a group that padding fills, for the encodings of bytes
+	fn group_chars(&self) -> Option<usize> {
+		match self.alphabet {
+			Alphabet::Base32 | Alphabet::Base32Hex => Some(8),
+			Alphabet::Base64 | Alphabet::Base64Url => Some(4),
+			Alphabet::Base58 | Alphabet::Crockford => None
+		}
+	}
+
+	fn name(&self) -> &str {
+		match self.alphabet {
+			Alphabet::Base58 => "base 58",
+			Alphabet::Base32 | Alphabet::Base32Hex | Alphabet::Crockford => "base 32",
+			Alphabet::Base64 | Alphabet::Base64Url => "base 64"
+		}
+	}
+
+	fn digit_value(&self, c: char) -> Option<u8> {
+		// Crockford's base 32 ignores case and reads the letters that look like 0 and 1 as those digits
+		let c = match (self.alphabet, c.to_ascii_uppercase()) {
+			(Alphabet::Crockford, 'O') => '0',
+			(Alphabet::Crockford, 'I' | 'L') => '1',
+			(Alphabet::Crockford | Alphabet::Base32 | Alphabet::Base32Hex, upper) => upper,
+			_ => c
+		};
+		self.chars().iter().position(|b| *b as char == c).map(|d| d as u8)
+	}
+}
+
+/// Writes the value in bits in the encoding
+pub fn encode(bits: &BitVec, encoding: BaseEncoding) -> String {
+	let mut bytes = bits_to_bytes(bits);
+	if !encoding.zeros {
+		let first = bytes.iter().position(|b| *b != 0).unwrap_or(bytes.len().saturating_sub(1));
+		bytes.drain(..first);
+	}
+	let chars = encoding.chars();
+	match encoding.alphabet {
+		Alphabet::Base58 => {
+			let zeros = bytes.iter().take_while(|b| **b == 0).count();
+			// digits of the number in base 58, least significant first
+			let mut digits: Vec<u32> = Vec::new();
+			for byte in &bytes[zeros..] {
+				let mut carry = *byte as u32;
+				for digit in digits.iter_mut() {
+					carry += *digit << 8;
+					*digit = carry % 58;
+					carry /= 58;
+				}
+				while carry > 0 {
+					digits.push(carry % 58);
+					carry /= 58;
+				}
+			}
+			let mut ret = "1".repeat(zeros);
+			ret.extend(digits.iter().rev().map(|d| chars[*d as usize] as char));
+			if ret.is_empty() {
+				ret.push('1');
+			}
+			ret
+		}
+		Alphabet::Crockford => {
+			// the number is split into 5 bit digits from its least significant bit
+			let bits = bytes_to_bits(&bytes);
+			let pad = (5 - bits.len() % 5) % 5;
+			let padded: Vec<bool> = std::iter::repeat_n(false, pad).chain(bits).collect();
+			let mut ret: String = padded.chunks(5).map(|digit| chars[digit.iter().fold(0, |n, b| (n << 1) | *b as usize)] as char).collect();
+			if !encoding.zeros {
+				let first = ret.find(|c| c != '0').unwrap_or(ret.len().saturating_sub(1));
+				ret.drain(..first);
+			}
+			if ret.is_empty() {
+				ret.push('0');
+			}
+			ret
+		}
+		Alphabet::Base32 | Alphabet::Base32Hex | Alphabet::Base64 | Alphabet::Base64Url => {
+			if bytes.is_empty() {
+				bytes.push(0);
+			}
+			// the bytes are split into digits from the first bit, and the last digit is filled with zeroes
+			let width = if encoding.group_chars() == Some(8) { 5 } else { 6 };
+			let bits = bytes_to_bits(&bytes);
+			let mut ret: String = bits.chunks(width).map(|digit| {
+				let value = digit.iter().fold(0, |n, b| (n << 1) | *b as usize) << (width - digit.len());
+				chars[value] as char
+			}).collect();
+			if encoding.pad {
+				while !ret.len().is_multiple_of(encoding.group_chars().unwrap()) {
+					ret.push('=');
+				}
+			}
+			ret
+		}
+	}
+}
+
+/// Reads the value written in arg in the encoding
+pub fn decode(arg: &str, encoding: BaseEncoding) -> Result<BitVec, String> {
+	let invalid_char = |c: char| format!("Character {} not allowed in {}", c, encoding.name());
+	let mut bits = BitVec::new();
+	match encoding.alphabet {
+		Alphabet::Base58 => {
+			let zeros = arg.chars().take_while(|c| *c == '1').count();
+			// bytes of the number, least significant first
+			let mut bytes: Vec<u32> = Vec::new();
+			for c in arg.chars().skip(zeros) {
+				let mut carry = encoding.digit_value(c).ok_or(invalid_char(c))? as u32;
+				for byte in bytes.iter_mut() {
+					carry += *byte * 58;
+					*byte = carry & 0xFF;
+					carry >>= 8;
+				}
+				while carry > 0 {
+					bytes.push(carry & 0xFF);
+					carry >>= 8;
+				}
+			}
+			bytes.extend(std::iter::repeat_n(0, zeros));
+			bits.extend(bytes.iter().rev().flat_map(|b| bits_of(*b as u128, 8)));
+		}
+		Alphabet::Crockford => {
+			for c in arg.chars().filter(|c| *c != '-') {
+				bits.extend(bits_of(encoding.digit_value(c).ok_or(invalid_char(c))? as u128, 5));
+			}
+		}
+		Alphabet::Base32 | Alphabet::Base32Hex | Alphabet::Base64 | Alphabet::Base64Url => {
+			let group = encoding.group_chars().unwrap();
+			let width = if group == 8 { 5 } else { 6 };
+			let digits = arg.trim_end_matches('=');
+			let padding = arg.len() - digits.len();
+			if padding > 0 && !arg.len().is_multiple_of(group) {
+				return Err(format!("Padding of {} does not fill a group of {} characters", arg, group));
+			}
+			for c in digits.chars() {
+				bits.extend(bits_of(encoding.digit_value(c).ok_or(invalid_char(c))? as u128, width as u32));
+			}
+			// the bits left over from the last byte must be zeroes, and there must be fewer than a whole digit of them
+			let extra = bits.len() % 8;
+			if extra >= width || padding >= group || bits.iter().rev().take(extra).any(|b| *b) {
+				return Err(format!("{} is not valid {}", arg, encoding.name()));
+			}
+			bits.truncate(bits.len() - extra);
+		}
+	}
+	if arg.is_empty() {
+		return Err(format!("Missing {} digits", encoding.name()));
+	}
+	Ok(bits)
+}
+
+#[cfg(test)]
+mod tests {
+	use crate::baseenc::*;
+
+	fn enc(param: &str) -> BaseEncoding {
+		BaseEncoding::parse(param).unwrap()
+	}
+
+	#[test]
+	fn base64_tests() {
+		assert_eq!(encode(&bytes_to_bits(b"foob"), enc("base64")), "Zm9vYg==");
+		assert_eq!(encode(&bytes_to_bits(b"foob"), enc("base64:nopad")), "Zm9vYg");
+		assert_eq!(encode(&bytes_to_bits(&[0xFB, 0xFF]), enc("base64url")), "-_8");
+		assert_eq!(encode(&bytes_to_bits(&[0, 1, 0, 1]), enc("base64")), "AQAB");
+		assert_eq!(encode(&bytes_to_bits(&[0, 1, 0, 1]), enc("base64:zeros")), "AAEAAQ==");
+		assert_eq!(decode("Zm9vYg==", enc("base64")), Ok(bytes_to_bits(b"foob")));
+		assert_eq!(decode("Zm9vYg", enc("base64")), Ok(bytes_to_bits(b"foob")));
+		assert!(decode("Zm9vYh==", enc("base64")).is_err());
+		assert!(decode("Zm9vYg=", enc("base64")).is_err());
+		assert!(decode("Zm9vY", enc("base64")).is_err());
+		assert!(decode("-_8", enc("base64")).is_err());
+	}
+
+	#[test]
+	fn base32_tests() {
+		assert_eq!(encode(&bytes_to_bits(b"foobar"), enc("base32")), "MZXW6YTBOI======");
+		assert_eq!(encode(&bytes_to_bits(b"foobar"), enc("base32hex:nopad")), "CPNMUOJ1E8");
+		assert_eq!(decode("mzxw6ytboi======", enc("base32")), Ok(bytes_to_bits(b"foobar")));
+		assert_eq!(encode(&bytes_to_bits(&[0x01, 0x00]), enc("crockford")), "80");
+		assert_eq!(encode(&bytes_to_bits(&[0x00, 0x20]), enc("crockford:zeros")), "0010");
+		assert_eq!(decode("8O", enc("crockford")), Ok(bytes_to_bits(&[0x01, 0x00]).split_off(6)));
+		assert!(decode("U", enc("crockford")).is_err());
+		assert!(BaseEncoding::parse("crockford:pad").is_err());
+	}
+
+	#[test]
+	fn base58_tests() {
+		assert_eq!(encode(&bytes_to_bits(b"Hello World!"), enc("base58")), "2NEpo7TZRRrLZSi2U");
+		assert_eq!(encode(&bytes_to_bits(&[0, 0, 0x28, 0x7F, 0xB4, 0xCD]), enc("base58:zeros")), "11233QC4");
+		assert_eq!(encode(&bytes_to_bits(&[0, 0, 0x28, 0x7F, 0xB4, 0xCD]), enc("base58")), "233QC4");
+		assert_eq!(encode(&bytes_to_bits(&[0]), enc("base58")), "1");
+		assert_eq!(decode("11233QC4", enc("base58")), Ok(bytes_to_bits(&[0, 0, 0x28, 0x7F, 0xB4, 0xCD])));
+		assert!(decode("0OIl", enc("base58")).is_err());
+	}
+}
@@ -0,0 +1,182 @@
+use bitvec::prelude::BitVec;
+
+use crate::bytes::bits_of;
+
+/// How the digits of a binary coded decimal number are laid out
+#[derive(PartialEq, Eq, Debug)]
+#[derive(Clone, Copy)]
+pub enum BcdKind {
+	/// two digits per byte, e.g. 1234 is 0x1234, with a trailing sign nibble in signed mode
+	Packed,
+	/// one digit per byte in the low nibble, e.g. 1234 is 0x01020304
+	Unpacked,
+	/// one digit per byte with an 0xF zone nibble, where the last zone is the sign, e.g. 1234 is 0xF1F2F3F4 (or 0xF1F2F3C4 signed)
+	Zoned
+}
+
+impl BcdKind {
+	pub fn parse(param: &str) -> Result<BcdKind, String> {
+		match param {
+			"packed" => Ok(BcdKind::Packed),
+			"unpacked" => Ok(BcdKind::Unpacked),
+			"zoned" => Ok(BcdKind::Zoned),
+			_ => Err(format!("Unknown BCD kind: {}", param))
+		}
+	}
+}
+
+/// Sign nibbles, where 0xF marks an unsigned number
+const PLUS: u8 = 0xC;
+const MINUS: u8 = 0xD;
+const UNSIGNED: u8 = 0xF;
+
+/// Reads a sign nibble, where 0xB and 0xD are negative and the other non-digits are positive
+fn sign_of(nibble: u8) -> Option<bool> {
+	match nibble {
+		0xB | 0xD => Some(true),
+		0xA | 0xC | 0xE | 0xF => Some(false),
+		_ => None
+	}
+}
+
+/// Reads the BCD number written in hexadecimal in arg (without its prefix)
+/// Returns whether the number is negative and its decimal digits
+pub fn read_bcd(arg: &str, kind: BcdKind) -> Result<(bool, String), String> {
+	let mut nibbles = Vec::new();
+	for c in arg.chars() {
+		match c.to_digit(16) {
+			Some(nibble) => nibbles.push(nibble as u8),
+			None => return Err(format!("Character {} not allowed in BCD", c))
+		}
+	}
+	if nibbles.is_empty() {
+		return Err("Missing BCD digits".to_string());
+	}
+	let invalid = |nibble: u8| format!("Invalid BCD nibble {:X} in {}", nibble, arg);
+
+	let mut is_negative = false;
+	let mut digits = String::new();
+	match kind {
+		BcdKind::Packed => {
+			if let Some(sign) = sign_of(*nibbles.last().unwrap()) {
+				is_negative = sign;
+				nibbles.pop();
+			}
+			for nibble in nibbles {
+				if nibble > 9 {
+					return Err(invalid(nibble));
+				}
+				digits.push(char::from(b'0' + nibble));
+			}
+		}
+		BcdKind::Unpacked | BcdKind::Zoned => {
+			if nibbles.len() % 2 == 1 {
+				nibbles.insert(0, 0);
+			}
+			let byte_count = nibbles.len() / 2;
+			for (index, byte) in nibbles.chunks(2).enumerate() {
+				let (zone, digit) = (byte[0], byte[1]);
+				let zone_ok = match kind {
+					BcdKind::Unpacked => zone == 0,
+					_ if index + 1 == byte_count => match sign_of(zone) {
+						Some(sign) => {
+							is_negative = sign;
+							true
+						}
+						None => false
+					},
+					_ => zone == UNSIGNED
+				};
+				if !zone_ok {
+					return Err(invalid(zone));
+				}
+				if digit > 9 {
+					return Err(invalid(digit));
+				}
+				digits.push(char::from(b'0' + digit));
+			}
+		}
+	}
+	if digits.is_empty() {
+		return Err("Missing BCD digits".to_string());
+	}
+	Ok((is_negative, digits))
+}
+
+/// Encodes the decimal digits of a number in BCD
+/// Packed numbers only get a sign nibble in signed mode, and zoned numbers mark unsigned numbers with an 0xF sign zone
+/// Unpacked numbers have nowhere to keep a sign, so negative numbers give an Err
+pub fn write_bcd(digits: &str, is_negative: bool, kind: BcdKind, signed_mode: bool) -> Result<BitVec, String> {
+	// zero padded to a fixed width is written without any digits
+	let digits = if digits.is_empty() { "0" } else { digits };
+	let sign = if !signed_mode { UNSIGNED } else if is_negative { MINUS } else { PLUS };
+	let mut nibbles: Vec<u8> = Vec::new();
+	match kind {
+		BcdKind::Packed => {
+			nibbles.extend(digits.bytes().map(|b| b - b'0'));
+			if signed_mode {
+				nibbles.push(sign);
+			}
+			if nibbles.len() % 2 == 1 {
+				nibbles.insert(0, 0);
+			}
+		}
+		BcdKind::Unpacked => {
+			if is_negative {
+				return Err("Unpacked BCD has no sign, so it cannot hold negative numbers".to_string());
+			}
+			for b in digits.bytes() {
+				nibbles.extend([0, b - b'0']);
+			}
+		}
+		BcdKind::Zoned => {
+			for b in digits.bytes() {
+				nibbles.extend([UNSIGNED, b - b'0']);
+			}
+			let last_zone = nibbles.len() - 2;
+			nibbles[last_zone] = sign;
+		}
+	}
+	Ok(nibbles.iter().flat_map(|nibble| bits_of(*nibble as u128, 4)).collect())
+}
+
+#[cfg(test)]
+mod tests {
+	use crate::bcd::*;
+
+	fn hex(bits: &BitVec) -> String {
+		bits.chunks(4).map(|nibble| char::from_digit(nibble.iter().fold(0, |n, b| (n << 1) | *b as u32), 16).unwrap().to_ascii_uppercase()).collect()
+	}
+
+	#[test]
+	fn read_bcd_tests() {
+		assert_eq!(read_bcd("1234", BcdKind::Packed), Ok((false, "1234".to_string())));
+		assert_eq!(read_bcd("01234D", BcdKind::Packed), Ok((true, "01234".to_string())));
+		assert_eq!(read_bcd("01020304", BcdKind::Unpacked), Ok((false, "1234".to_string())));
+		assert_eq!(read_bcd("F1F2F3C4", BcdKind::Zoned), Ok((false, "1234".to_string())));
+		assert_eq!(read_bcd("F1F2F3D4", BcdKind::Zoned), Ok((true, "1234".to_string())));
+		assert!(read_bcd("12A4", BcdKind::Packed).is_err());
+		assert!(read_bcd("C", BcdKind::Packed).is_err());
+		assert!(read_bcd("0112", BcdKind::Unpacked).is_err());
+		assert!(read_bcd("010A", BcdKind::Unpacked).is_err());
+		assert!(read_bcd("C1F2", BcdKind::Zoned).is_err());
+		assert!(read_bcd("F112", BcdKind::Zoned).is_err());
+	}
+
+	#[test]
+	fn write_bcd_tests() {
+		assert_eq!(hex(&write_bcd("1234", false, BcdKind::Packed, false).unwrap()), "1234");
+		assert_eq!(hex(&write_bcd("123", false, BcdKind::Packed, false).unwrap()), "0123");
+		assert_eq!(hex(&write_bcd("1234", true, BcdKind::Packed, true).unwrap()), "01234D");
+		assert_eq!(hex(&write_bcd("123", false, BcdKind::Packed, true).unwrap()), "123C");
+		assert_eq!(hex(&write_bcd("1234", false, BcdKind::Unpacked, false).unwrap()), "01020304");
+		assert_eq!(hex(&write_bcd("1234", false, BcdKind::Zoned, false).unwrap()), "F1F2F3F4");
+		assert_eq!(hex(&write_bcd("1234", true, BcdKind::Zoned, true).unwrap()), "F1F2F3D4");
+		assert!(write_bcd("12", true, BcdKind::Unpacked, true).is_err());
+		assert_eq!(hex(&write_bcd("", false, BcdKind::Packed, false).unwrap()), "00");
+		assert_eq!(hex(&write_bcd("", false, BcdKind::Packed, true).unwrap()), "0C");
+		assert_eq!(hex(&write_bcd("", false, BcdKind::Unpacked, false).unwrap()), "00");
+		assert_eq!(hex(&write_bcd("", false, BcdKind::Zoned, false).unwrap()), "F0");
+		assert_eq!(hex(&write_bcd("0", false, BcdKind::Zoned, true).unwrap()), "C0");
+	}
+}
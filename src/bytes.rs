@@ -0,0 +1,55 @@
+use bitvec::prelude::{BitSlice, BitVec};
+
+/// Gets the bits of the bytes, the most significant bit of the first byte first
+pub fn bytes_to_bits(bytes: &[u8]) -> BitVec {
+	bytes.iter().flat_map(|byte| (0..8).rev().map(move |i| (byte >> i) & 1 == 1)).collect()
+}
+
+/// Gets the bytes of the bits, which are padded at the front to a whole number of bytes
+pub fn bits_to_bytes(bits: &BitSlice) -> Vec<u8> {
+	let pad = (8 - bits.len() % 8) % 8;
+	let padded: Vec<bool> = std::iter::repeat_n(false, pad).chain(bits.iter().map(|b| *b)).collect();
+	padded.chunks(8).map(|byte| byte.iter().fold(0, |n, b| (n << 1) | *b as u8)).collect()
+}
+
+/// Writes the low len bits of num into a new bitvec
+pub fn bits_of(num: u128, len: u32) -> BitVec {
+	(0..len).rev().map(|i| (num >> i) & 1 == 1).collect()
+}
+
+/// Reads the bits as an unsigned number
+pub fn num_of(bits: &BitSlice) -> u128 {
+	bits.iter().fold(0, |num, bit| (num << 1) | *bit as u128)
+}
+
+/// Reads the bytes written in hexadecimal in arg (without its prefix), which may have whitespace between the bytes
+pub fn hex_bytes(arg: &str) -> Result<Vec<u8>, String> {
+	let mut bytes = Vec::new();
+	for word in arg.split_whitespace() {
+		if !word.len().is_multiple_of(2) {
+			return Err(format!("{} is not a whole number of bytes", arg));
+		}
+		let nibbles: Vec<u8> = word.chars().map(|c| c.to_digit(16).map(|n| n as u8).ok_or(format!("Character {} not allowed in hexadecimal bytes", c))).collect::<Result<_, _>>()?;
+		bytes.extend(nibbles.chunks(2).map(|byte| (byte[0] << 4) | byte[1]));
+	}
+	if bytes.is_empty() {
+		return Err(format!("{} is not a whole number of bytes", arg));
+	}
+	Ok(bytes)
+}
+
+#[cfg(test)]
+mod tests {
+	use crate::bytes::*;
+
+	#[test]
+	fn bytes_tests() {
+		assert_eq!(bits_to_bytes(&bytes_to_bits(&[0x12, 0xAB])), vec![0x12, 0xAB]);
+		assert_eq!(bits_to_bytes(&bits_of(0x1FF, 9)), vec![0x01, 0xFF]);
+		assert_eq!(num_of(&bits_of(0x5A, 8)), 0x5A);
+		assert_eq!(num_of(&bits_of(0x5A, 4)), 0xA);
+		assert_eq!(hex_bytes("12AB cd"), Ok(vec![0x12, 0xAB, 0xCD]));
+		assert!(hex_bytes("1 2").is_err());
+		assert!(hex_bytes("").is_err());
+	}
+}
@@ -0,0 +1,284 @@
+use std::cmp::Ordering;
+
+/// An exact signed decimal number with a fractional part, stored as a string of digits
+/// Used where binary fractions must be converted to decimal without any rounding
+#[derive(PartialEq, Eq, Hash, Debug)]
+#[derive(Clone)]
+pub struct Decimal {
+	pub negative: bool,
+	/// all digits of the number, most significant first
+	pub digits: Vec<u8>,
+	/// how many of the digits are after the decimal point
+	pub frac_len: usize
+}
+
+impl Decimal {
+	/// Creates a decimal from an integer string of digits
+	pub fn from_digits(negative: bool, digits: &str) -> Decimal {
+		let mut ret = Decimal { negative, digits: digits.bytes().map(|b| b - b'0').collect(), frac_len: 0 };
+		ret.normalize();
+		ret
+	}
+
+	/// Parses a string like "12.345", "-0.5" or ".25"
+	pub fn parse(arg: &str) -> Result<Decimal, String> {
+		let (negative, arg) = match arg.strip_prefix('-') {
+			Some(arg) => (true, arg),
+			None => (false, arg)
+		};
+		let (int, frac) = arg.split_once('.').unwrap_or((arg, ""));
+		if int.is_empty() && frac.is_empty() {
+			return Err("Empty decimal number".to_string());
+		}
+
+		let mut digits = Vec::new();
+		for c in int.chars().chain(frac.chars()) {
+			match c.to_digit(10) {
+				Some(d) => digits.push(d as u8),
+				None => return Err(format!("Character {} not allowed in decimal numbers", c))
+			}
+		}
+
+		let mut ret = Decimal { negative, digits, frac_len: frac.len() };
+		ret.normalize();
+		Ok(ret)
+	}
+
+	/// Removes leading zeroes of the integer part and trailing zeroes of the fractional part
+	fn normalize(&mut self) {
+		while self.frac_len > 0 && self.digits.last() == Some(&0) {
+			self.digits.pop();
+			self.frac_len -= 1;
+		}
+		while self.digits.len() > self.frac_len && self.digits.first() == Some(&0) {
+			self.digits.remove(0);
+		}
+		if self.is_zero() {
+			self.negative = false;
+		}
+	}
+
+	pub fn is_zero(&self) -> bool {
+		self.digits.iter().all(|d| *d == 0)
+	}
+
+	/// Gets the digits before the decimal point as a string, or "0" if there are none
+	pub fn int_str(&self) -> String {
+		let int: String = self.digits[..self.digits.len() - self.frac_len].iter().map(|d| (d + b'0') as char).collect();
+		if int.is_empty() { "0".to_string() } else { int }
+	}
+
+	/// Gets the fractional part as a positive decimal less than one
+	pub fn frac(&self) -> Decimal {
+		let mut ret = Decimal { negative: false, digits: self.digits[self.digits.len() - self.frac_len..].to_vec(), frac_len: self.frac_len };
+		ret.normalize();
+		ret
+	}
+
+	/// Compares the magnitudes of two decimals
+	pub fn cmp_magnitude(&self, other: &Decimal) -> Ordering {
+		let (a, b) = Decimal::align(self, other);
+		a.cmp(&b)
+	}
+
+	/// Gets the digits of both numbers padded to the same number of integer and fractional digits
+	fn align(a: &Decimal, b: &Decimal) -> (Vec<u8>, Vec<u8>) {
+		let frac_len = a.frac_len.max(b.frac_len);
+		let int_len = (a.digits.len() - a.frac_len).max(b.digits.len() - b.frac_len);
+		let pad = |d: &Decimal| {
+			let mut digits = vec![0; int_len - (d.digits.len() - d.frac_len)];
+			digits.extend_from_slice(&d.digits);
+			digits.resize(int_len + frac_len, 0);
+			digits
+		};
+		(pad(a), pad(b))
+	}
+
+	/// Adds two decimals
+	pub fn add(&self, other: &Decimal) -> Decimal {
+		let frac_len = self.frac_len.max(other.frac_len);
+		let (a, b) = Decimal::align(self, other);
+
+		let mut ret = if self.negative == other.negative {
+			// add magnitudes
+			let mut digits = vec![0; a.len()];
+			let mut carry = 0;
+			for i in (0..a.len()).rev() {
+				let sum = a[i] + b[i] + carry;
+				digits[i] = sum % 10;
+				carry = sum / 10;
+			}
+			if carry > 0 {
+				digits.insert(0, carry);
+			}
+			Decimal { negative: self.negative, digits, frac_len }
+		} else {
+			// subtract the smaller magnitude from the larger
+			let (big, small, negative) = if a >= b { (a, b, self.negative) } else { (b, a, other.negative) };
+			let mut digits = vec![0; big.len()];
+			let mut borrow = 0;
+			for i in (0..big.len()).rev() {
+				let mut diff = big[i] as i8 - small[i] as i8 - borrow;
+				borrow = 0;
+				if diff < 0 {
+					diff += 10;
+					borrow = 1;
+				}
+				digits[i] = diff as u8;
+			}
+			Decimal { negative, digits, frac_len }
+		};
+		ret.normalize();
+		ret
+	}
+
+	/// Subtracts other from this decimal
+	pub fn sub(&self, other: &Decimal) -> Decimal {
+		let mut other = other.clone();
+		other.negative = !other.negative;
+		self.add(&other)
+	}
+
+	/// Multiplies the decimal by a small integer
+	pub fn mul_small(&self, m: u32) -> Decimal {
+		let mut digits = vec![0; self.digits.len()];
+		let mut carry = 0u64;
+		for i in (0..self.digits.len()).rev() {
+			let prod = self.digits[i] as u64 * m as u64 + carry;
+			digits[i] = (prod % 10) as u8;
+			carry = prod / 10;
+		}
+		while carry > 0 {
+			digits.insert(0, (carry % 10) as u8);
+			carry /= 10;
+		}
+		let mut ret = Decimal { negative: self.negative, digits, frac_len: self.frac_len };
+		ret.normalize();
+		ret
+	}
+
+	/// Divides the decimal by two, which is always exact with one more fractional digit
+	pub fn halve(&self) -> Decimal {
+		let mut digits = self.digits.clone();
+		let mut frac_len = self.frac_len;
+		if digits.last().is_some_and(|d| d % 2 == 1) {
+			digits.push(0);
+			frac_len += 1;
+		}
+		let mut carry = 0;
+		for digit in digits.iter_mut() {
+			let cur = carry * 10 + *digit;
+			*digit = cur / 2;
+			carry = cur % 2;
+		}
+		let mut ret = Decimal { negative: self.negative, digits, frac_len };
+		ret.normalize();
+		ret
+	}
+
+	/// Divides the integer part of the decimal by a small integer, giving the quotient and the remainder of the magnitude
+	pub fn div_small(&self, d: u32) -> (Decimal, u32) {
+		let mut digits = Vec::new();
+		let mut rem = 0u64;
+		for digit in &self.digits[..self.digits.len() - self.frac_len] {
+			let cur = rem * 10 + *digit as u64;
+			digits.push((cur / d as u64) as u8);
+			rem = cur % d as u64;
+		}
+		let mut ret = Decimal { negative: self.negative, digits, frac_len: 0 };
+		ret.normalize();
+		(ret, rem as u32)
+	}
+
+	/// Gets the decimal with the opposite sign
+	pub fn negate(&self) -> Decimal {
+		let mut ret = self.clone();
+		ret.negative = !ret.negative && !ret.is_zero();
+		ret
+	}
+
+	/// Multiplies (positive places) or divides (negative places) the decimal by a power of ten by moving the decimal point
+	pub fn move_point(&self, places: i64) -> Decimal {
+		let mut ret = self.clone();
+		let places_len = places.unsigned_abs() as usize;
+		if places >= 0 {
+			let moved = places_len.min(ret.frac_len);
+			ret.frac_len -= moved;
+			ret.digits.extend(std::iter::repeat_n(0, places_len - moved));
+		} else {
+			ret.frac_len += places_len;
+			let missing = ret.frac_len.saturating_sub(ret.digits.len());
+			ret.digits.splice(0..0, std::iter::repeat_n(0, missing));
+		}
+		ret.normalize();
+		ret
+	}
+
+	/// Multiplies (positive shift) or divides (negative shift) the decimal by a power of two
+	pub fn shift(&self, shift: i64) -> Decimal {
+		let mut ret = self.clone();
+		if shift >= 0 {
+			for _ in 0..shift {
+				ret = ret.mul_small(2);
+			}
+		} else {
+			for _ in 0..-shift {
+				ret = ret.halve();
+			}
+		}
+		ret
+	}
+}
+
+impl std::fmt::Display for Decimal {
+	fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+		if self.negative {
+			write!(f, "-")?;
+		}
+		write!(f, "{}", self.int_str())?;
+		if self.frac_len > 0 {
+			write!(f, ".")?;
+			for d in &self.digits[self.digits.len() - self.frac_len..] {
+				write!(f, "{}", d)?;
+			}
+		}
+		Ok(())
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use crate::decimal::*;
+
+	fn dec(s: &str) -> Decimal {
+		Decimal::parse(s).unwrap()
+	}
+
+	#[test]
+	fn parse_display_tests() {
+		assert_eq!(dec("0012.3400").to_string(), "12.34");
+		assert_eq!(dec(".5").to_string(), "0.5");
+		assert_eq!(dec("-0.0").to_string(), "0");
+		assert_eq!(dec("-7").to_string(), "-7");
+		assert!(Decimal::parse("1.2.3").is_err());
+		assert!(Decimal::parse("").is_err());
+	}
+
+	#[test]
+	fn arithmetic_tests() {
+		assert_eq!(dec("0.7071").shift(15).to_string(), "23170.2528");
+		assert_eq!(dec("23170").shift(-15).to_string(), "0.70709228515625");
+		assert_eq!(dec("1.25").move_point(3).to_string(), "1250");
+		assert_eq!(dec("12.5").move_point(-3).to_string(), "0.0125");
+		assert_eq!(dec("1.5").add(&dec("-2.25")).to_string(), "-0.75");
+		assert_eq!(dec("0.1").sub(&dec("0.1")).to_string(), "0");
+		assert_eq!(dec("99.9").add(&dec("0.1")).to_string(), "100");
+		assert_eq!(dec("12.5").mul_small(16).to_string(), "200");
+		assert_eq!(dec("-3.75").frac().to_string(), "0.75");
+		assert_eq!(dec("-3.75").int_str(), "3");
+		assert_eq!(dec("0.5").cmp_magnitude(&dec("-0.50")), Ordering::Equal);
+		assert_eq!(dec("-1234").div_small(10), (dec("-123"), 4));
+		assert_eq!(dec("7").div_small(8), (dec("0"), 7));
+		assert_eq!(dec("0").negate().to_string(), "0");
+	}
+}
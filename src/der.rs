@@ -0,0 +1,180 @@
+use bitvec::prelude::BitVec;
+
+use crate::negative;
+use crate::bytes::{bits_to_bytes, bytes_to_bits, hex_bytes};
+
+const INTEGER: u8 = 0x02;
+const OBJECT_IDENTIFIER: u8 = 0x06;
+
+/// Wraps the value in a tag and a DER length, which is one byte below 128 and otherwise 0x80 plus the number of length bytes
+fn tlv(tag: u8, value: &[u8]) -> Vec<u8> {
+	let mut ret = vec![tag];
+	if value.len() < 0x80 {
+		ret.push(value.len() as u8);
+	} else {
+		let len_bytes: Vec<u8> = value.len().to_be_bytes().into_iter().skip_while(|b| *b == 0).collect();
+		ret.push(0x80 | len_bytes.len() as u8);
+		ret.extend(len_bytes);
+	}
+	ret.extend(value);
+	ret
+}
+
+/// Gets the value of a DER encoding with the expected tag
+fn value_of(bytes: &[u8], tag: u8) -> Result<&[u8], String> {
+	if bytes.len() < 2 {
+		return Err("DER encoding is missing its tag or length".to_string());
+	}
+	if bytes[0] != tag {
+		return Err(format!("Expected DER tag 0x{:02X} but found 0x{:02X}", tag, bytes[0]));
+	}
+	let (len, header_len) = if bytes[1] < 0x80 {
+		(bytes[1] as usize, 2)
+	} else {
+		let len_bytes = (bytes[1] & 0x7F) as usize;
+		if len_bytes == 0 || len_bytes > 8 || bytes.len() < 2 + len_bytes {
+			return Err("DER lengths must be definite and fit in 8 bytes".to_string());
+		}
+		let len = bytes[2..2 + len_bytes].iter().fold(0usize, |n, b| (n << 8) | *b as usize);
+		if bytes[2] == 0 || len < 0x80 {
+			return Err("DER lengths must be as short as possible".to_string());
+		}
+		(len, 2 + len_bytes)
+	};
+	if bytes.len() - header_len != len {
+		return Err(format!("DER length is {} but {} bytes follow", len, bytes.len() - header_len));
+	}
+	Ok(&bytes[header_len..])
+}
+
+/// Encodes the value of the bits (signed if signed_mode) as a DER INTEGER, in the fewest two's complement bytes
+pub fn encode_integer(bits: &BitVec, signed_mode: bool) -> Vec<u8> {
+	let sign = signed_mode && bits.first().is_some_and(|b| *b);
+	// sign-extend to whole bytes, with room for a sign bit in unsigned mode
+	let mut extended: BitVec = BitVec::new();
+	let pad = 8 + (8 - bits.len() % 8) % 8;
+	extended.extend(std::iter::repeat_n(sign, pad));
+	extended.extend(bits.iter().map(|b| *b));
+	let mut bytes = bits_to_bytes(&extended);
+
+	// a leading byte is redundant if it and the top bit of the next byte are all sign bits
+	while bytes.len() > 1 && ((bytes[0] == 0x00 && bytes[1] & 0x80 == 0) || (bytes[0] == 0xFF && bytes[1] & 0x80 != 0)) {
+		bytes.remove(0);
+	}
+	tlv(INTEGER, &bytes)
+}
+
+/// Decodes the DER INTEGER written in hexadecimal in arg
+/// Returns whether the number is negative and the bits of its magnitude
+pub fn decode_integer(arg: &str) -> Result<(bool, BitVec), String> {
+	let bytes = hex_bytes(arg)?;
+	let value = value_of(&bytes, INTEGER)?;
+	if value.is_empty() {
+		return Err("DER INTEGER has no value bytes".to_string());
+	}
+	if value.len() > 1 && ((value[0] == 0x00 && value[1] & 0x80 == 0) || (value[0] == 0xFF && value[1] & 0x80 != 0)) {
+		return Err("DER INTEGER has a redundant leading byte".to_string());
+	}
+	let mut bits = bytes_to_bits(value);
+	let is_negative = bits[0];
+	if is_negative {
+		negative(&mut bits);
+	}
+	Ok((is_negative, bits))
+}
+
+/// Encodes an OID such as 1.2.840.113549 as a DER OBJECT IDENTIFIER
+/// The first two arcs share a subidentifier of 40 times the first plus the second, and each subidentifier is written 7 bits per byte
+pub fn encode_oid(arg: &str) -> Result<Vec<u8>, String> {
+	let arcs: Vec<u128> = arg.split('.').map(|arc| arc.parse::<u128>().map_err(|_| format!("Invalid OID arc {} in {}", arc, arg))).collect::<Result<_, _>>()?;
+	if arcs.len() < 2 || arcs[0] > 2 || (arcs[0] < 2 && arcs[1] >= 40) {
+		return Err(format!("{} is not an OID, which starts with 0, 1 or 2 and then below 40 unless the first arc is 2", arg));
+	}
+	let first = (arcs[0] * 40).checked_add(arcs[1]).ok_or(format!("OID arc {} is too large", arcs[1]))?;
+	let mut value = Vec::new();
+	for mut arc in std::iter::once(first).chain(arcs[2..].iter().copied()) {
+		let mut subidentifier = vec![(arc & 0x7F) as u8];
+		arc >>= 7;
+		while arc != 0 {
+			subidentifier.insert(0, 0x80 | (arc & 0x7F) as u8);
+			arc >>= 7;
+		}
+		value.extend(subidentifier);
+	}
+	Ok(tlv(OBJECT_IDENTIFIER, &value))
+}
+
+/// Decodes the DER OBJECT IDENTIFIER in bytes into its dotted form
+pub fn decode_oid(bytes: &[u8]) -> Result<String, String> {
+	let value = value_of(bytes, OBJECT_IDENTIFIER)?;
+	if value.is_empty() || value.last().unwrap() & 0x80 != 0 {
+		return Err("DER OBJECT IDENTIFIER ends in the middle of an arc".to_string());
+	}
+	let mut arcs = Vec::new();
+	let mut arc: u128 = 0;
+	let mut arc_start = true;
+	for byte in value {
+		if arc_start && *byte == 0x80 {
+			return Err("DER OBJECT IDENTIFIER arcs must not start with 0x80".to_string());
+		}
+		if arc >> 121 != 0 {
+			return Err("OID arc is too large".to_string());
+		}
+		arc = (arc << 7) | (byte & 0x7F) as u128;
+		arc_start = byte & 0x80 == 0;
+		if arc_start {
+			if arcs.is_empty() {
+				let first = (arc / 40).min(2);
+				arcs.push(first);
+				arcs.push(arc - first * 40);
+			} else {
+				arcs.push(arc);
+			}
+			arc = 0;
+		}
+	}
+	Ok(arcs.iter().map(|arc| arc.to_string()).collect::<Vec<_>>().join("."))
+}
+
+#[cfg(test)]
+mod tests {
+	use crate::bytes::{bits_of, num_of};
+	use crate::der::*;
+
+	fn value_of_decoded(decoded: (bool, BitVec)) -> i128 {
+		let magnitude = num_of(&decoded.1) as i128;
+		if decoded.0 { -magnitude } else { magnitude }
+	}
+
+	#[test]
+	fn integer_tests() {
+		assert_eq!(encode_integer(&bits_of(65537, 24), false), vec![0x02, 0x03, 0x01, 0x00, 0x01]);
+		assert_eq!(encode_integer(&bits_of(128, 8), false), vec![0x02, 0x02, 0x00, 0x80]);
+		assert_eq!(encode_integer(&bits_of(0, 8), false), vec![0x02, 0x01, 0x00]);
+		assert_eq!(encode_integer(&bits_of(-128i128 as u128, 16), true), vec![0x02, 0x01, 0x80]);
+		assert_eq!(encode_integer(&bits_of(-129i128 as u128, 16), true), vec![0x02, 0x02, 0xFF, 0x7F]);
+		assert_eq!(encode_integer(&bits_of(-1i128 as u128, 32), true), vec![0x02, 0x01, 0xFF]);
+		assert_eq!(encode_integer(&BitVec::repeat(true, 1024), false)[..5], [0x02, 0x81, 0x81, 0x00, 0xFF]);
+		assert_eq!(decode_integer("0203010001").map(value_of_decoded), Ok(65537));
+		assert_eq!(decode_integer("02020080").map(value_of_decoded), Ok(128));
+		assert_eq!(decode_integer("0202FF7F").map(value_of_decoded), Ok(-129));
+		assert!(decode_integer("02020001").is_err());
+		assert!(decode_integer("0202FF80").is_err());
+		assert!(decode_integer("020301").is_err());
+		assert!(decode_integer("0401FF").is_err());
+	}
+
+	#[test]
+	fn oid_tests() {
+		assert_eq!(encode_oid("1.2.840.113549"), Ok(vec![0x06, 0x06, 0x2A, 0x86, 0x48, 0x86, 0xF7, 0x0D]));
+		assert_eq!(encode_oid("2.999.3"), Ok(vec![0x06, 0x03, 0x88, 0x37, 0x03]));
+		assert_eq!(decode_oid(&[0x06, 0x06, 0x2A, 0x86, 0x48, 0x86, 0xF7, 0x0D]), Ok("1.2.840.113549".to_string()));
+		assert_eq!(decode_oid(&[0x06, 0x03, 0x88, 0x37, 0x03]), Ok("2.999.3".to_string()));
+		assert_eq!(decode_oid(&encode_oid("2.25.329800735698586629295641978511506172918").unwrap()), Ok("2.25.329800735698586629295641978511506172918".to_string()));
+		assert!(encode_oid("1.40").is_err());
+		assert!(encode_oid("3.1").is_err());
+		assert!(encode_oid("1").is_err());
+		assert!(decode_oid(&[0x06, 0x02, 0x2A, 0x86]).is_err());
+		assert!(decode_oid(&[0x06, 0x02, 0x80, 0x01]).is_err());
+	}
+}
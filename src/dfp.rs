@@ -0,0 +1,445 @@
+use bitvec::prelude::BitVec;
+
+use crate::bytes::{bits_of, num_of};
+
+/// How the coefficient of a decimal float is stored
+#[derive(PartialEq, Eq, Debug)]
+#[derive(Clone, Copy)]
+pub enum Encoding {
+	/// binary integer decimal, the coefficient as one binary integer
+	Bid,
+	/// densely packed decimal, the coefficient as a leading digit and declets of three digits each
+	Dpd
+}
+
+/// An IEEE 754-2008 decimal floating point format (decimal32, decimal64 or decimal128)
+#[derive(PartialEq, Eq, Debug)]
+#[derive(Clone, Copy)]
+pub struct DecFormat {
+	/// number of coefficient digits
+	pub digits: u32,
+	/// number of exponent continuation bits after the 5 bit combination field
+	pub exp_bits: u32,
+	pub encoding: Encoding
+}
+
+pub const DECIMAL32: DecFormat = DecFormat { digits: 7, exp_bits: 6, encoding: Encoding::Bid };
+pub const DECIMAL64: DecFormat = DecFormat { digits: 16, exp_bits: 8, encoding: Encoding::Bid };
+pub const DECIMAL128: DecFormat = DecFormat { digits: 34, exp_bits: 12, encoding: Encoding::Bid };
+
+/// What kind of value a decimal float bit pattern holds
+#[derive(PartialEq, Eq, Debug)]
+#[derive(Clone, Copy)]
+pub enum DecClass {
+	Finite,
+	Infinite,
+	Nan,
+	SignalingNan
+}
+
+/// A decoded decimal float, where finite values are coefficient * 10^exp
+#[derive(PartialEq, Eq, Debug)]
+pub struct DecDecoded {
+	pub is_negative: bool,
+	pub class: DecClass,
+	pub coefficient: u128,
+	pub exp: i64
+}
+
+impl DecFormat {
+	/// Parses a format name such as "decimal64" or "d128", optionally followed by ":bid" (the default) or ":dpd"
+	pub fn parse(param: &str) -> Result<DecFormat, String> {
+		let (name, encoding) = match param.split_once(':') {
+			Some((name, "bid")) => (name, Encoding::Bid),
+			Some((name, "dpd")) => (name, Encoding::Dpd),
+			Some((_, encoding)) => return Err(format!("Unknown decimal float encoding: {}", encoding)),
+			None => (param, Encoding::Bid)
+		};
+		let mut format = match name {
+			"32" | "d32" | "decimal32" => DECIMAL32,
+			"64" | "d64" | "decimal64" => DECIMAL64,
+			"128" | "d128" | "decimal128" => DECIMAL128,
+			_ => return Err(format!("Unknown decimal float format: {}", name))
+		};
+		format.encoding = encoding;
+		Ok(format)
+	}
+
+	/// Total number of bits in the format
+	pub fn width(&self) -> u64 {
+		1 + 5 + self.exp_bits as u64 + self.trailing_bits() as u64
+	}
+
+	/// Number of bits of the coefficient after the combination field, 10 for every three digits
+	fn trailing_bits(&self) -> u32 {
+		10 * (self.digits - 1) / 3
+	}
+
+	fn emax(&self) -> i64 {
+		3 << (self.exp_bits - 1)
+	}
+
+	fn bias(&self) -> i64 {
+		self.emax() + self.digits as i64 - 2
+	}
+
+	/// Smallest exponent of the coefficient
+	fn qmin(&self) -> i64 {
+		1 - self.emax() - self.digits as i64 + 1
+	}
+
+	/// Largest exponent of the coefficient
+	fn qmax(&self) -> i64 {
+		self.emax() - self.digits as i64 + 1
+	}
+}
+
+/// Packs three decimal digits into a 10 bit declet
+fn encode_declet(d2: u32, d1: u32, d0: u32) -> u32 {
+	// bits of the digits from the most significant, as used in the tables of IEEE 754-2008
+	let bit = |d: u32, i: u32| (d >> i) & 1;
+	let (b, c, d) = (bit(d2, 2), bit(d2, 1), bit(d2, 0));
+	let (f, g, h) = (bit(d1, 2), bit(d1, 1), bit(d1, 0));
+	let (j, k, m) = (bit(d0, 2), bit(d0, 1), bit(d0, 0));
+	let pack = |bits: [u32; 10]| bits.iter().fold(0, |declet, bit| (declet << 1) | bit);
+	match (d2 >= 8, d1 >= 8, d0 >= 8) {
+		(false, false, false) => pack([b, c, d, f, g, h, 0, j, k, m]),
+		(false, false, true) => pack([b, c, d, f, g, h, 1, 0, 0, m]),
+		(false, true, false) => pack([b, c, d, j, k, h, 1, 0, 1, m]),
+		(true, false, false) => pack([j, k, d, f, g, h, 1, 1, 0, m]),
+		(true, true, false) => pack([j, k, d, 0, 0, h, 1, 1, 1, m]),
+		(true, false, true) => pack([f, g, d, 0, 1, h, 1, 1, 1, m]),
+		(false, true, true) => pack([b, c, d, 1, 0, h, 1, 1, 1, m]),
+		(true, true, true) => pack([0, 0, d, 1, 1, h, 1, 1, 1, m])
+	}
+}
+
+/// Unpacks a 10 bit declet into its three decimal digits, accepting the non-canonical declets too
+fn decode_declet(declet: u32) -> (u32, u32, u32) {
+	let b = |i: u32| (declet >> i) & 1;
+	let small = |hi: u32, mid: u32, lo: u32| (hi << 2) | (mid << 1) | lo;
+	let large = |lo: u32| 8 | lo;
+	if b(3) == 0 {
+		return (small(b(9), b(8), b(7)), small(b(6), b(5), b(4)), small(b(2), b(1), b(0)));
+	}
+	match (b(2), b(1), b(6), b(5)) {
+		(0, 0, _, _) => (small(b(9), b(8), b(7)), small(b(6), b(5), b(4)), large(b(0))),
+		(0, 1, _, _) => (small(b(9), b(8), b(7)), large(b(4)), small(b(6), b(5), b(0))),
+		(1, 0, _, _) => (large(b(7)), small(b(6), b(5), b(4)), small(b(9), b(8), b(0))),
+		(1, 1, 0, 0) => (large(b(7)), large(b(4)), small(b(9), b(8), b(0))),
+		(1, 1, 0, 1) => (large(b(7)), small(b(9), b(8), b(4)), large(b(0))),
+		(1, 1, 1, 0) => (small(b(9), b(8), b(7)), large(b(4)), large(b(0))),
+		_ => (large(b(7)), large(b(4)), large(b(0)))
+	}
+}
+
+/// Rounds off the last drop digits of the coefficient digits, to the nearest with ties to even
+fn round_digits(digits: &[u8], drop: usize) -> Vec<u8> {
+	if drop > digits.len() {
+		return Vec::new();
+	}
+	let keep = digits.len() - drop;
+	let mut ret = digits[..keep].to_vec();
+	if drop == 0 {
+		return ret;
+	}
+
+	let half = digits[keep];
+	let sticky = digits[keep + 1..].iter().any(|d| *d != 0);
+	let odd = ret.last().is_some_and(|d| d % 2 == 1);
+	if half > 5 || (half == 5 && (sticky || odd)) {
+		// add one, growing if it carries out
+		let mut carried = true;
+		for digit in ret.iter_mut().rev() {
+			if *digit == 9 {
+				*digit = 0;
+			} else {
+				*digit += 1;
+				carried = false;
+				break;
+			}
+		}
+		if carried {
+			ret.insert(0, 1);
+		}
+	}
+	ret
+}
+
+/// Gets the bit pattern of an infinity or nan
+fn special(is_negative: bool, class: DecClass, format: DecFormat) -> BitVec {
+	let mut ret = BitVec::new();
+	ret.push(is_negative);
+	let (combination, signaling) = match class {
+		DecClass::Infinite => (0b11110, false),
+		DecClass::Nan => (0b11111, false),
+		DecClass::SignalingNan => (0b11111, true),
+		DecClass::Finite => panic!()
+	};
+	ret.extend(bits_of(combination, 5));
+	ret.push(signaling);
+	ret.extend(bits_of(0, format.exp_bits - 1 + format.trailing_bits()));
+	ret
+}
+
+/// Encodes coefficient * 10^exp, where the coefficient has at most the format's digits and exp is in range
+pub fn encode(is_negative: bool, coefficient: u128, exp: i64, format: DecFormat) -> BitVec {
+	let mut ret = BitVec::new();
+	ret.push(is_negative);
+	let biased = (exp + format.bias()) as u128;
+	let trailing_bits = format.trailing_bits();
+
+	match format.encoding {
+		Encoding::Bid => {
+			if coefficient >> (trailing_bits + 3) == 0 {
+				ret.extend(bits_of(biased, format.exp_bits + 2));
+				ret.extend(bits_of(coefficient, trailing_bits + 3));
+			} else {
+				// large coefficients start with an implied 0b100
+				ret.extend(bits_of(0b11, 2));
+				ret.extend(bits_of(biased, format.exp_bits + 2));
+				ret.extend(bits_of(coefficient, trailing_bits + 1));
+			}
+		}
+		Encoding::Dpd => {
+			let digits: Vec<u32> = format!("{:0width$}", coefficient, width = format.digits as usize).chars().map(|c| c.to_digit(10).unwrap()).collect();
+			let exp_high = biased >> format.exp_bits;
+			let combination = if digits[0] < 8 {
+				(exp_high << 3) | digits[0] as u128
+			} else {
+				0b11000 | (exp_high << 1) | (digits[0] as u128 & 1)
+			};
+			ret.extend(bits_of(combination, 5));
+			ret.extend(bits_of(biased, format.exp_bits));
+			for chunk in digits[1..].chunks(3) {
+				ret.extend(bits_of(encode_declet(chunk[0], chunk[1], chunk[2]) as u128, 10));
+			}
+		}
+	}
+	ret
+}
+
+/// Decodes the decimal float bit pattern in bits, which must be as wide as the format
+/// Non-canonical coefficients too large for the format are read as zero
+pub fn decode(bits: &BitVec, format: DecFormat) -> DecDecoded {
+	let is_negative = bits[0];
+	let combination = num_of(&bits[1..6]);
+	let decoded = |class, coefficient, exp| DecDecoded { is_negative, class, coefficient, exp };
+	if combination == 0b11110 || combination == 0b11111 {
+		let class = match (combination, bits[6]) {
+			(0b11110, _) => DecClass::Infinite,
+			(_, false) => DecClass::Nan,
+			(_, true) => DecClass::SignalingNan
+		};
+		return decoded(class, 0, 0);
+	}
+
+	let exp_bits = format.exp_bits as usize;
+	let trailing = bits[6 + exp_bits..].to_bitvec();
+	let (biased, coefficient) = match format.encoding {
+		Encoding::Bid => {
+			if combination >> 3 != 0b11 {
+				(num_of(&bits[1..3 + exp_bits]), num_of(&bits[3 + exp_bits..]))
+			} else {
+				let low = num_of(&bits[5 + exp_bits..]);
+				(num_of(&bits[3..5 + exp_bits]), (0b100 << (format.trailing_bits() + 1)) | low)
+			}
+		}
+		Encoding::Dpd => {
+			let (exp_high, lead) = if combination >> 3 != 0b11 {
+				(combination >> 3, combination & 0b111)
+			} else {
+				((combination >> 1) & 0b11, 8 | (combination & 1))
+			};
+			let biased = (exp_high << exp_bits) | num_of(&bits[6..6 + exp_bits]);
+			let coefficient = trailing.chunks(10).fold(lead, |coefficient, declet| {
+				let (d2, d1, d0) = decode_declet(num_of(declet) as u32);
+				coefficient * 1000 + (d2 * 100 + d1 * 10 + d0) as u128
+			});
+			(biased, coefficient)
+		}
+	};
+	let coefficient = if coefficient >= 10u128.pow(format.digits) { 0 } else { coefficient };
+	decoded(DecClass::Finite, coefficient, biased as i64 - format.bias())
+}
+
+/// Parses a decimal string such as "123.45", "1.5e-3", "inf", "nan" or "snan" (with any '-' already removed)
+/// and encodes it in the format
+/// The exponent is kept as written where possible, so "1.50" and "1.5" give different bit patterns
+pub fn read_dfp(positive_arg: &str, is_negative: bool, format: DecFormat) -> Result<BitVec, String> {
+	match positive_arg.to_ascii_lowercase().as_str() {
+		"inf" | "infinity" => return Ok(special(is_negative, DecClass::Infinite, format)),
+		"nan" => return Ok(special(is_negative, DecClass::Nan, format)),
+		"snan" => return Ok(special(is_negative, DecClass::SignalingNan, format)),
+		_ => { }
+	}
+
+	let (mantissa, exp) = match positive_arg.split_once(['e', 'E']) {
+		Some((mantissa, exp)) => (mantissa, exp.parse::<i64>().map_err(|_| format!("Invalid exponent: {}", exp))?),
+		None => (positive_arg, 0)
+	};
+	let (int, frac) = mantissa.split_once('.').unwrap_or((mantissa, ""));
+	if int.is_empty() && frac.is_empty() {
+		return Err(format!("Missing digits in {}", positive_arg));
+	}
+	let mut digits = Vec::new();
+	for c in int.chars().chain(frac.chars()) {
+		match c.to_digit(10) {
+			Some(digit) => digits.push(digit as u8),
+			None => return Err(format!("Character {} not allowed in decimal numbers", c))
+		}
+	}
+	let first = digits.iter().position(|d| *d != 0).unwrap_or(digits.len());
+	let mut digits = digits[first..].to_vec();
+	let mut exp = exp - frac.len() as i64;
+
+	// round off digits that do not fit, or that are below the smallest exponent
+	let drop = (digits.len() as i64 - format.digits as i64).max(format.qmin() - exp).max(0);
+	if drop > 0 {
+		digits = round_digits(&digits, drop as usize);
+		exp += drop;
+		if digits.len() > format.digits as usize {
+			digits.pop();
+			exp += 1;
+		}
+	}
+
+	// exponents above the largest are brought down by padding the coefficient with zeroes, if it has room
+	if exp > format.qmax() {
+		let pad = exp - format.qmax();
+		if digits.is_empty() {
+			exp = format.qmax();
+		} else if digits.len() as i64 + pad <= format.digits as i64 {
+			digits.extend(std::iter::repeat_n(0, pad as usize));
+			exp = format.qmax();
+		} else {
+			return Ok(special(is_negative, DecClass::Infinite, format));
+		}
+	}
+
+	let coefficient = digits.iter().fold(0u128, |coefficient, digit| coefficient * 10 + *digit as u128);
+	Ok(encode(is_negative, coefficient, exp, format))
+}
+
+/// Gets the value of a decoded decimal float as a string, in scientific notation when the exponent is large
+fn value_str(decoded: &DecDecoded) -> String {
+	let sign = if decoded.is_negative { "-" } else { "" };
+	match decoded.class {
+		DecClass::Infinite => return format!("{}Infinity", sign),
+		DecClass::Nan => return format!("{}NaN", sign),
+		DecClass::SignalingNan => return format!("{}sNaN", sign),
+		DecClass::Finite => { }
+	}
+
+	let coefficient = decoded.coefficient.to_string();
+	let adjusted = decoded.exp + coefficient.len() as i64 - 1;
+	if decoded.exp <= 0 && adjusted >= -6 {
+		// plain notation, keeping the zeroes the exponent implies
+		let frac_len = (-decoded.exp) as usize;
+		if frac_len == 0 {
+			return format!("{}{}", sign, coefficient);
+		}
+		let padded = format!("{:0>width$}", coefficient, width = frac_len + 1);
+		let (int, frac) = padded.split_at(padded.len() - frac_len);
+		format!("{}{}.{}", sign, int, frac)
+	} else {
+		let (lead, rest) = coefficient.split_at(1);
+		let point = if rest.is_empty() { "" } else { "." };
+		format!("{}{}{}{}E{:+}", sign, lead, point, rest, adjusted)
+	}
+}
+
+/// Writes the value of the decimal float bit pattern in bits (which must be as wide as the format)
+pub fn write_dfp(bits: &BitVec, format: DecFormat) -> String {
+	value_str(&decode(bits, format))
+}
+
+/// Writes the value of the decimal float bit pattern in bits (which must be as wide as the format) and a breakdown of its fields
+pub fn write_fields(bits: &BitVec, format: DecFormat) -> String {
+	let decoded = decode(bits, format);
+	let bin = |bits: &BitVec| -> String { bits.iter().map(|b| if *b { '1' } else { '0' }).collect() };
+	let class = match decoded.class {
+		DecClass::Finite => "finite",
+		DecClass::Infinite => "infinite",
+		DecClass::Nan => "nan",
+		DecClass::SignalingNan => "snan"
+	};
+	let mut ret = format!("value={} class={} sign={} combination=0b{} continuation=0b{}",
+		value_str(&decoded),
+		class,
+		bits[0] as u8,
+		bin(&bits[1..6].to_bitvec()),
+		bin(&bits[6..6 + format.exp_bits as usize].to_bitvec()));
+	if decoded.class == DecClass::Finite {
+		ret.push_str(&format!(" exponent={} (biased {}) coefficient={}", decoded.exp, decoded.exp + format.bias(), decoded.coefficient));
+	}
+	ret
+}
+
+#[cfg(test)]
+mod tests {
+	use crate::dfp::*;
+
+	fn read_num(arg: &str, format: &str) -> u128 {
+		let format = DecFormat::parse(format).unwrap();
+		match arg.strip_prefix('-') {
+			Some(arg) => num_of(&read_dfp(arg, true, format).unwrap()),
+			None => num_of(&read_dfp(arg, false, format).unwrap())
+		}
+	}
+
+	fn write_num(num: u128, format: &str) -> String {
+		let format = DecFormat::parse(format).unwrap();
+		write_dfp(&bits_of(num, format.width() as u32), format)
+	}
+
+	#[test]
+	fn declet_tests() {
+		for d2 in 0..10 {
+			for d1 in 0..10 {
+				for d0 in 0..10 {
+					assert_eq!(decode_declet(encode_declet(d2, d1, d0)), (d2, d1, d0));
+				}
+			}
+		}
+		assert_eq!(encode_declet(0, 1, 2), 0x012);
+		assert_eq!(encode_declet(9, 9, 9), 0x0FF);
+		assert_eq!(decode_declet(0x3FF), (9, 9, 9));
+	}
+
+	#[test]
+	fn read_dfp_tests() {
+		assert_eq!(read_num("1", "d64"), 0x31C0000000000001);
+		assert_eq!(read_num("1", "d64:dpd"), 0x2238000000000001);
+		assert_eq!(read_num("-1", "d32"), 0xB2800001);
+		assert_eq!(read_num("123.45", "d64:dpd"), 0x22300000000049C5);
+		assert_eq!(read_num("1.50", "d32"), 0x31800096);
+		assert_eq!(read_num("9999999", "d32"), 0x6CB8967F);
+		assert_eq!(read_num("12345678", "d32"), 0x3312D688);
+		assert_eq!(read_num("1e96", "d32"), 0x5F8F4240);
+		assert_eq!(read_num("1e97", "d32"), 0x78000000);
+		assert_eq!(read_num("inf", "d128"), 0x7800_0000_0000_0000_0000_0000_0000_0000);
+		assert_eq!(read_num("0", "d128:dpd"), 0x2208_0000_0000_0000_0000_0000_0000_0000);
+		assert!(read_dfp("1.2.3", false, DECIMAL64).is_err());
+		assert!(DecFormat::parse("d64:bcd").is_err());
+	}
+
+	#[test]
+	fn write_dfp_tests() {
+		assert_eq!(write_num(0x31C0000000000001, "d64"), "1");
+		assert_eq!(write_num(0x22300000000049C5, "d64:dpd"), "123.45");
+		assert_eq!(write_num(0x31800096, "d32"), "1.50");
+		assert_eq!(write_num(0x5F8F4240, "d32"), "1.000000E+96");
+		assert_eq!(write_num(0x00000001, "d32"), "1E-101");
+		assert_eq!(write_num(0x7C000000, "d32"), "NaN");
+		assert_eq!(write_num(0xF8000000, "d32"), "-Infinity");
+		assert_eq!(write_num(0x6CB8967F + 1, "d32"), "0");
+	}
+
+	#[test]
+	fn fields_tests() {
+		let format = DecFormat::parse("d64:dpd").unwrap();
+		assert_eq!(write_fields(&bits_of(0x22300000000049C5, 64), format), "value=123.45 class=finite sign=0 combination=0b01000 continuation=0b10001100 exponent=-2 (biased 396) coefficient=12345");
+		assert_eq!(write_fields(&bits_of(0x7E000000, 32), DECIMAL32), "value=sNaN class=snan sign=0 combination=0b11111 continuation=0b100000");
+	}
+}
@@ -0,0 +1,151 @@
+use std::{fs, io::{self, Read, Write}};
+
+use crate::{bytes::hex_bytes, WriteSeparator};
+
+/// How bytes are laid out in a dump, like the options of xxd
+#[derive(Clone, Copy)]
+pub struct DumpFormat {
+	/// bytes per line
+	pub cols: usize,
+	/// bytes per group, where 0 puts the whole line in one group
+	pub group: usize,
+	/// the offset of the first byte dumped
+	pub seek: usize,
+	/// the most bytes dumped, or None to dump to the end
+	pub len: Option<usize>,
+	/// turns a dump back into bytes instead
+	pub reverse: bool
+}
+
+impl Default for DumpFormat {
+	fn default() -> DumpFormat {
+		DumpFormat { cols: 16, group: 2, seek: 0, len: None, reverse: false }
+	}
+}
+
+/// Parses a count of bytes written in decimal or in hexadecimal with the prefix 0x
+pub fn parse_count(param: &str) -> Result<usize, String> {
+	match param.strip_prefix("0x") {
+		Some(digits) => usize::from_str_radix(digits, 16),
+		None => param.parse()
+	}.map_err(|_| format!("Invalid number of bytes: {}", param))
+}
+
+/// Gets the character shown in the gutter for a byte, which is '.' unless the byte is printable ASCII
+fn gutter_char(byte: u8) -> char {
+	if (0x20..=0x7E).contains(&byte) { byte as char } else { '.' }
+}
+
+/// Writes the hexadecimal of the bytes of one line, with the separator between groups
+fn write_groups(bytes: &[u8], format: &DumpFormat, upper: bool, write_separator: &WriteSeparator) -> String {
+	let group = if format.group == 0 { format.cols } else { format.group };
+	let groups: Vec<String> = bytes.chunks(group).map(|group| {
+		group.iter().map(|byte| if upper { format!("{:02X}", byte) } else { format!("{:02x}", byte) }).collect()
+	}).collect();
+	match write_separator {
+		WriteSeparator::Separator(sep) => groups.join(sep),
+		WriteSeparator::RuntimeDetermine | WriteSeparator::None => groups.concat()
+	}
+}
+
+/// Dumps the bytes from the seek offset, one line of offset, hexadecimal groups and ASCII gutter per cols bytes
+/// e.g. '00000000: 4865 6C6C 6F0A                           Hello.'
+pub fn dump(bytes: &[u8], format: &DumpFormat, upper: bool, write_separator: &WriteSeparator) -> String {
+	let start = format.seek.min(bytes.len());
+	let end = format.len.map_or(bytes.len(), |len| start.saturating_add(len).min(bytes.len()));
+	// lines are padded to the width of a full line so the gutters line up
+	let width = write_groups(&vec![0; format.cols], format, upper, write_separator).chars().count();
+	let mut ret = String::new();
+	for (index, line) in bytes[start..end].chunks(format.cols).enumerate() {
+		let offset = start + index * format.cols;
+		let offset = if upper { format!("{:08X}", offset) } else { format!("{:08x}", offset) };
+		let gutter: String = line.iter().map(|byte| gutter_char(*byte)).collect();
+		ret.push_str(&format!("{}: {:width$}  {}\n", offset, write_groups(line, format, upper, write_separator), gutter, width = width));
+	}
+	ret
+}
+
+/// Turns a dump back into its bytes, putting the bytes of each line at its offset and filling any gap with zeroes
+/// Lines without an offset (plain hexadecimal, as written by 'xxd -p') follow on from the bytes before them
+pub fn undump(text: &str, write_separator: &WriteSeparator) -> Result<Vec<u8>, String> {
+	let mut bytes = Vec::new();
+	for (number, line) in text.lines().enumerate() {
+		let (offset, hex) = match line.split_once(':') {
+			Some((offset, rest)) => {
+				let offset = usize::from_str_radix(offset.trim(), 16).map_err(|_| format!("Invalid offset {} on line {}", offset, number + 1))?;
+				// the hexadecimal ends where the gutter starts, two spaces after it
+				(offset, rest.trim_start().split("  ").next().unwrap())
+			}
+			None => (bytes.len(), line)
+		};
+		let hex = match write_separator {
+			WriteSeparator::Separator(sep) if !sep.trim().is_empty() => hex.replace(sep.as_str(), ""),
+			_ => hex.to_string()
+		};
+		if hex.trim().is_empty() {
+			continue;
+		}
+		let line_bytes = hex_bytes(&hex).map_err(|err| format!("{} on line {}", err, number + 1))?;
+		if bytes.len() < offset {
+			bytes.resize(offset, 0);
+		}
+		for (index, byte) in line_bytes.into_iter().enumerate() {
+			match bytes.get_mut(offset + index) {
+				Some(old) => *old = byte,
+				None => bytes.push(byte)
+			}
+		}
+	}
+	Ok(bytes)
+}
+
+/// Dumps the file at path, or stdin if there is none, or turns a dump back into bytes and writes them to stdout
+pub fn run(path: Option<&str>, format: &DumpFormat, upper: bool, write_separator: &WriteSeparator) -> Result<(), String> {
+	let input = match path {
+		Some(path) => fs::read(path).map_err(|err| format!("Cannot read {}: {}", path, err))?,
+		None => {
+			let mut input = Vec::new();
+			io::stdin().read_to_end(&mut input).map_err(|err| format!("Cannot read stdin: {}", err))?;
+			input
+		}
+	};
+	// groups are separated by a space unless another separator is set
+	let write_separator = match write_separator {
+		WriteSeparator::RuntimeDetermine => &WriteSeparator::Separator(" ".to_string()),
+		_ => write_separator
+	};
+	let output = if format.reverse {
+		let text = String::from_utf8(input).map_err(|_| "A dump must be text".to_string())?;
+		undump(&text, write_separator)?
+	} else {
+		dump(&input, format, upper, write_separator).into_bytes()
+	};
+	io::stdout().write_all(&output).map_err(|err| format!("Cannot write to stdout: {}", err))
+}
+
+#[cfg(test)]
+mod tests {
+	use crate::dump::*;
+
+	#[test]
+	fn dump_tests() {
+		let space = WriteSeparator::Separator(" ".to_string());
+		let format = DumpFormat::default();
+		assert_eq!(dump(b"Hello\n", &format, false, &space), "00000000: 4865 6c6c 6f0a                           Hello.\n");
+		assert_eq!(dump(b"0123456789ABCDEFG", &format, true, &space), "00000000: 3031 3233 3435 3637 3839 4142 4344 4546  0123456789ABCDEF\n00000010: 47                                       G\n");
+		let format = DumpFormat { cols: 4, group: 1, seek: 1, len: Some(5), reverse: false };
+		assert_eq!(dump(b"\x00ABCDEFG", &format, true, &WriteSeparator::Separator(":".to_string())), "00000001: 41:42:43:44  ABCD\n00000005: 45           E\n");
+		assert_eq!(dump(b"", &DumpFormat::default(), true, &space), "");
+	}
+
+	#[test]
+	fn undump_tests() {
+		let space = WriteSeparator::Separator(" ".to_string());
+		assert_eq!(undump("00000000: 4865 6c6c 6f0a                           Hello.\n", &space), Ok(b"Hello\n".to_vec()));
+		assert_eq!(undump("00000000: 3031 3233 3435 3637 3839 4142 4344 4546  0123456789ABCDEF\n00000010: 47  G\n", &space), Ok(b"0123456789ABCDEFG".to_vec()));
+		assert_eq!(undump("00000002: 41:42  AB", &WriteSeparator::Separator(":".to_string())), Ok(vec![0, 0, 0x41, 0x42]));
+		assert_eq!(undump("48656c\n6c6f", &WriteSeparator::None), Ok(b"Hello".to_vec()));
+		assert!(undump("00000000: 486  H", &space).is_err());
+		assert!(undump("zz: 48  H", &space).is_err());
+	}
+}
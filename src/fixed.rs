@@ -0,0 +1,177 @@
+use std::fmt;
+
+use bitvec::prelude::BitVec;
+
+use crate::{decimal::Decimal, negative, read, write, ReadMode, WriteLength, WriteMode, WriteSeparator};
+
+/// How real numbers that fall between two fixed-point values are rounded
+#[derive(PartialEq, Eq, Debug)]
+#[derive(Clone, Copy)]
+pub enum Rounding {
+	Nearest,
+	Truncate,
+	Floor
+}
+
+/// A Qm.n fixed-point format with m integer bits (including the sign bit in signed mode) and n fractional bits
+#[derive(PartialEq, Eq, Debug)]
+#[derive(Clone, Copy)]
+pub struct QFormat {
+	pub int_bits: u64,
+	pub frac_bits: u64,
+	pub rounding: Rounding,
+	pub report_error: bool
+}
+
+impl Rounding {
+	pub fn parse(param: &str) -> Result<Rounding, String> {
+		match param {
+			"nearest" => Ok(Rounding::Nearest),
+			"trunc" | "truncate" => Ok(Rounding::Truncate),
+			"floor" => Ok(Rounding::Floor),
+			_ => Err(format!("Unknown rounding mode: {}", param))
+		}
+	}
+}
+
+impl QFormat {
+	/// Parses a format written as "m.n", e.g. "1.15"
+	pub fn parse(param: &str, rounding: Rounding, report_error: bool) -> Result<QFormat, String> {
+		let parse_bits = |s: &str| s.parse::<u64>().map_err(|_| format!("Invalid Q format: {}", param));
+		let (int_bits, frac_bits) = match param.split_once('.') {
+			Some((m, n)) => (parse_bits(m)?, parse_bits(n)?),
+			None => return Err(format!("Expected <m>.<n> but found {}", param))
+		};
+		if int_bits + frac_bits == 0 {
+			return Err(format!("Invalid Q format: {}", param));
+		}
+		Ok(QFormat { int_bits, frac_bits, rounding, report_error })
+	}
+
+	/// Total number of bits in the format
+	pub fn width(&self) -> u64 {
+		self.int_bits + self.frac_bits
+	}
+}
+
+impl fmt::Display for QFormat {
+	fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+		write!(f, "Q{}.{}", self.int_bits, self.frac_bits)
+	}
+}
+
+/// Adds one to the unsigned integer in bits, growing it if needed
+fn increment(bits: &mut BitVec) {
+	for index in (0..bits.len()).rev() {
+		let bit = bits[index];
+		bits.set(index, !bit);
+		if !bit {
+			return;
+		}
+	}
+	bits.insert(0, true);
+}
+
+/// Encodes the real number arg (with any '-' already removed) in the fixed-point format q
+/// Returns the magnitude of the fixed-point integer, which the caller negates if the number is negative
+pub fn read_fixed(arg: &str, is_negative: bool, q: QFormat, signed_mode: bool) -> Result<BitVec, String> {
+	let scaled = Decimal::parse(arg)?.shift(q.frac_bits as i64);
+
+	let frac = scaled.frac();
+	let round_up = match q.rounding {
+		Rounding::Truncate => false,
+		Rounding::Floor => is_negative && !frac.is_zero(),
+		Rounding::Nearest => frac.cmp_magnitude(&Decimal::parse("0.5")?).is_ge()
+	};
+
+	let mut bits = read(&scaled.int_str(), ReadMode::Decimal, WriteMode::Binary, WriteLength::Unfixed, false)?;
+	if round_up {
+		increment(&mut bits);
+	}
+
+	// check that the magnitude fits, where negative numbers may reach one further than positive ones
+	let magnitude_bits = (bits.len() - bits.leading_zeros()) as u64;
+	let fits = if !signed_mode {
+		magnitude_bits <= q.width()
+	} else if is_negative {
+		magnitude_bits < q.width() || (magnitude_bits == q.width() && bits.count_ones() == 1)
+	} else {
+		magnitude_bits < q.width()
+	};
+	if !fits {
+		return Err(format!("{} is out of range for {}", arg, q));
+	}
+
+	Ok(bits)
+}
+
+/// Converts the fixed-point integer in bits into the real number it represents in the format q
+/// Only the low bits that make up the format are used, so the sign is the top bit of the format and not of a wider fixed width
+pub fn write_fixed(bits: &BitVec, q: QFormat, signed_mode: bool) -> String {
+	let width = q.width() as usize;
+	let mut magnitude = bits[bits.len().saturating_sub(width)..].to_bitvec();
+	while magnitude.len() < width {
+		magnitude.insert(0, false);
+	}
+	let is_negative = signed_mode && magnitude.first().is_some_and(|b| *b);
+	if is_negative {
+		negative(&mut magnitude);
+	}
+	let int_str = write(&mut magnitude, WriteMode::Decimal, &WriteSeparator::None, false, false);
+	Decimal::from_digits(is_negative, &int_str).shift(-(q.frac_bits as i64)).to_string()
+}
+
+/// Gets the exact difference between the fixed-point value in bits and the real number arg it was read from
+pub fn quantisation_error(arg: &str, bits: &BitVec, q: QFormat, signed_mode: bool) -> Result<String, String> {
+	let value = Decimal::parse(&write_fixed(bits, q, signed_mode))?;
+	Ok(value.sub(&Decimal::parse(arg)?).to_string())
+}
+
+#[cfg(test)]
+mod tests {
+	use bitvec::prelude::*;
+
+	use crate::fixed::*;
+
+	fn q(param: &str, rounding: Rounding) -> QFormat {
+		QFormat::parse(param, rounding, false).unwrap()
+	}
+
+	#[test]
+	fn read_fixed_tests() {
+		assert_eq!(read_fixed("0.7071", false, q("1.15", Rounding::Nearest), true), Ok(bitvec![1, 0, 1, 1, 0, 1, 0, 1, 0, 0, 0, 0, 0, 1, 0]));
+		assert_eq!(read_fixed("0.75", false, q("0.2", Rounding::Nearest), false), Ok(bitvec![1, 1]));
+
+		// 0.3 * 4 = 1.2
+		assert_eq!(read_fixed("0.3", false, q("2.2", Rounding::Nearest), false), Ok(bitvec![1]));
+		assert_eq!(read_fixed("0.3", true, q("2.2", Rounding::Truncate), true), Ok(bitvec![1]));
+		assert_eq!(read_fixed("0.3", true, q("2.2", Rounding::Floor), true), Ok(bitvec![1, 0]));
+		// 0.4 * 4 = 1.6
+		assert_eq!(read_fixed("0.4", false, q("2.2", Rounding::Nearest), false), Ok(bitvec![1, 0]));
+		assert_eq!(read_fixed("0.4", false, q("2.2", Rounding::Floor), false), Ok(bitvec![1]));
+	}
+
+	#[test]
+	fn range_tests() {
+		assert!(read_fixed("1", false, q("1.15", Rounding::Nearest), true).is_err());
+		assert!(read_fixed("1", true, q("1.15", Rounding::Nearest), true).is_ok());
+		assert!(read_fixed("1.5", true, q("1.15", Rounding::Nearest), true).is_err());
+		assert!(read_fixed("1.5", false, q("1.15", Rounding::Nearest), false).is_ok());
+		assert!(read_fixed("2", false, q("1.15", Rounding::Nearest), false).is_err());
+		assert!(QFormat::parse("0.0", Rounding::Nearest, false).is_err());
+		assert!(QFormat::parse("15", Rounding::Nearest, false).is_err());
+	}
+
+	#[test]
+	fn write_fixed_tests() {
+		let bits = bitvec![0, 1, 0, 1, 1, 0, 1, 0, 1, 0, 0, 0, 0, 0, 1, 0];
+		assert_eq!(write_fixed(&bits, q("1.15", Rounding::Nearest), true), "0.70709228515625");
+		assert_eq!(write_fixed(&bitvec![1, 0, 0, 0], q("1.3", Rounding::Nearest), true), "-1");
+		assert_eq!(write_fixed(&bitvec![1, 1, 1, 1], q("1.3", Rounding::Nearest), true), "-0.125");
+		assert_eq!(write_fixed(&bitvec![1, 1, 1, 1], q("1.3", Rounding::Nearest), false), "1.875");
+		// a fixed width wider than the format does not move the sign bit
+		let wide = crate::bytes::bits_of(0xC000, 32);
+		assert_eq!(write_fixed(&wide, q("1.15", Rounding::Nearest), true), "-0.5");
+		assert_eq!(quantisation_error("0.7071", &bits, q("1.15", Rounding::Nearest), true), Ok("-0.00000771484375".to_string()));
+	}
+}
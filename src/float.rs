@@ -0,0 +1,655 @@
+use bitvec::prelude::BitVec;
+
+use crate::{bytes::{bits_of, num_of}, decimal::Decimal, negative, read, write, ReadMode, WriteLength, WriteMode, WriteSeparator};
+
+/// The rules a floating point format follows for its special values
+#[derive(PartialEq, Eq, Debug)]
+#[derive(Clone, Copy)]
+pub enum FloatKind {
+	/// IEEE 754 style, with infinities and nans in the all ones exponent
+	Ieee,
+	/// OCP fp8 E4M3 style, with no infinities and a single nan per sign (all ones exponent and mantissa)
+	FiniteNan,
+	/// posit with the given total number of bits, where exp_bits is es
+	Posit(u32)
+}
+
+/// A binary floating point format with a sign bit, exp_bits of biased exponent and mant_bits of stored mantissa
+/// saturate makes numbers too large for the format become the largest finite value instead of an infinity or nan
+#[derive(PartialEq, Eq, Debug)]
+#[derive(Clone, Copy)]
+pub struct FloatFormat {
+	pub kind: FloatKind,
+	pub exp_bits: u32,
+	pub mant_bits: u32,
+	pub saturate: bool
+}
+
+pub const F16: FloatFormat = FloatFormat { kind: FloatKind::Ieee, exp_bits: 5, mant_bits: 10, saturate: false };
+pub const BF16: FloatFormat = FloatFormat { kind: FloatKind::Ieee, exp_bits: 8, mant_bits: 7, saturate: false };
+pub const TF32: FloatFormat = FloatFormat { kind: FloatKind::Ieee, exp_bits: 8, mant_bits: 10, saturate: false };
+pub const F32: FloatFormat = FloatFormat { kind: FloatKind::Ieee, exp_bits: 8, mant_bits: 23, saturate: false };
+pub const F64: FloatFormat = FloatFormat { kind: FloatKind::Ieee, exp_bits: 11, mant_bits: 52, saturate: false };
+pub const E4M3: FloatFormat = FloatFormat { kind: FloatKind::FiniteNan, exp_bits: 4, mant_bits: 3, saturate: false };
+pub const E5M2: FloatFormat = FloatFormat { kind: FloatKind::Ieee, exp_bits: 5, mant_bits: 2, saturate: false };
+
+/// What kind of value a float bit pattern holds
+#[derive(PartialEq, Eq, Debug)]
+#[derive(Clone, Copy)]
+pub enum FloatClass {
+	Zero,
+	Subnormal,
+	Normal,
+	Infinite,
+	Nan
+}
+
+/// A decoded float bit pattern, where finite values are significand * 2^exp
+#[derive(PartialEq, Eq, Debug)]
+pub struct Decoded {
+	pub is_negative: bool,
+	pub class: FloatClass,
+	pub significand: BitVec,
+	pub exp: i64
+}
+
+impl FloatFormat {
+	/// Parses a format name such as "f32", "bf16", "e4m3" or "posit<16,1>", optionally followed by ":sat"
+	pub fn parse(param: &str) -> Result<FloatFormat, String> {
+		let (name, saturate) = match param.strip_suffix(":sat") {
+			Some(name) => (name, true),
+			None => (param, false)
+		};
+		let mut format = match name {
+			"16" | "f16" | "fp16" | "half" => F16,
+			"bf16" => BF16,
+			"tf32" => TF32,
+			"32" | "f32" => F32,
+			"64" | "f64" => F64,
+			"e4m3" | "fp8e4m3" => E4M3,
+			"e5m2" | "fp8e5m2" => E5M2,
+			_ => match name.strip_prefix("posit") {
+				Some(posit) => FloatFormat::parse_posit(posit).ok_or(format!("Invalid posit format: {}", param))?,
+				None => return Err(format!("Unknown float format: {}", param))
+			}
+		};
+		format.saturate = saturate;
+		Ok(format)
+	}
+
+	/// Parses what follows "posit": "<N,es>", "N,es" or just "N" (with es = 2)
+	fn parse_posit(posit: &str) -> Option<FloatFormat> {
+		let posit = posit.strip_prefix('<').and_then(|p| p.strip_suffix('>')).unwrap_or(posit);
+		let (n, es) = match posit.split_once(',') {
+			Some((n, es)) => (n.trim().parse::<u32>().ok()?, es.trim().parse::<u32>().ok()?),
+			None => (posit.parse::<u32>().ok()?, 2)
+		};
+		if !(2..=64).contains(&n) || es > 8 {
+			return None;
+		}
+		Some(FloatFormat { kind: FloatKind::Posit(n), exp_bits: es, mant_bits: 0, saturate: false })
+	}
+
+	/// Total number of bits in the format
+	pub fn width(&self) -> u64 {
+		match self.kind {
+			FloatKind::Posit(n) => n as u64,
+			FloatKind::Ieee | FloatKind::FiniteNan => 1 + self.exp_bits as u64 + self.mant_bits as u64
+		}
+	}
+
+	fn bias(&self) -> i64 {
+		(1i64 << (self.exp_bits - 1)) - 1
+	}
+
+	/// The largest unbiased exponent of finite numbers
+	fn max_exp(&self) -> i64 {
+		match self.kind {
+			FloatKind::FiniteNan => self.bias() + 1,
+			FloatKind::Ieee | FloatKind::Posit(_) => self.bias()
+		}
+	}
+}
+
+/// Drops the low drop bits of the integer in bits, rounding to the nearest result with ties going to even
+pub fn round_shift(bits: &BitVec, drop: usize) -> BitVec {
+	if drop > bits.len() {
+		return BitVec::new();
+	}
+	let keep = bits.len() - drop;
+	let mut ret = bits[..keep].to_bitvec();
+	if drop == 0 {
+		return ret;
+	}
+
+	let half = bits[keep];
+	let sticky = bits[keep + 1..].any();
+	let odd = ret.last().is_some_and(|b| *b);
+	if half && (sticky || odd) {
+		// add one, growing if it carries out
+		let mut carried = true;
+		for index in (0..ret.len()).rev() {
+			let bit = ret[index];
+			ret.set(index, !bit);
+			if !bit {
+				carried = false;
+				break;
+			}
+		}
+		if carried {
+			ret.insert(0, true);
+		}
+	}
+	ret
+}
+
+/// Gets the bit pattern of an infinity, or a nan if is_nan
+/// Formats without infinities give a nan instead, or the largest finite value when saturating. Posits give NaR
+fn special(is_negative: bool, is_nan: bool, format: FloatFormat) -> BitVec {
+	if !is_nan && format.saturate && !matches!(format.kind, FloatKind::Posit(_)) {
+		return max_finite(is_negative, format);
+	}
+
+	let mut ret = BitVec::new();
+	match format.kind {
+		FloatKind::Posit(n) => {
+			ret.push(true);
+			ret.extend(bits_of(0, n - 1));
+		}
+		FloatKind::Ieee => {
+			// quiet nan
+			ret.push(is_negative);
+			ret.extend(bits_of(u128::MAX, format.exp_bits));
+			ret.push(is_nan);
+			ret.extend(bits_of(0, format.mant_bits - 1));
+		}
+		FloatKind::FiniteNan => {
+			ret.push(is_negative);
+			ret.extend(bits_of(u128::MAX, format.exp_bits + format.mant_bits));
+		}
+	}
+	ret
+}
+
+/// Gets the bit pattern of the largest finite number of an IEEE or fp8 format
+fn max_finite(is_negative: bool, format: FloatFormat) -> BitVec {
+	let mut ret = BitVec::new();
+	ret.push(is_negative);
+	match format.kind {
+		FloatKind::FiniteNan => {
+			ret.extend(bits_of(u128::MAX, format.exp_bits + format.mant_bits - 1));
+			ret.push(false);
+		}
+		FloatKind::Ieee | FloatKind::Posit(_) => {
+			ret.extend(bits_of(u128::MAX, format.exp_bits - 1));
+			ret.push(false);
+			ret.extend(bits_of(u128::MAX, format.mant_bits));
+		}
+	}
+	ret
+}
+
+/// Encodes the number significand * 2^exp in the format, rounding to the nearest value with ties to even
+/// significand may have leading zeroes
+pub fn encode(is_negative: bool, significand: &BitVec, exp: i64, format: FloatFormat) -> BitVec {
+	let significand = match significand.first_one() {
+		Some(first) => significand[first..].to_bitvec(),
+		None => {
+			// zero, which is unsigned in posits
+			let mut ret = BitVec::new();
+			ret.push(is_negative && !matches!(format.kind, FloatKind::Posit(_)));
+			ret.extend(bits_of(0, format.width() as u32 - 1));
+			return ret;
+		}
+	};
+
+	match format.kind {
+		FloatKind::Posit(n) => encode_posit(is_negative, &significand, exp, n, format.exp_bits),
+		FloatKind::Ieee | FloatKind::FiniteNan => encode_ieee(is_negative, &significand, exp, format)
+	}
+}
+
+/// Encodes the nonzero number significand * 2^exp (with no leading zeroes) in an IEEE or fp8 format
+fn encode_ieee(is_negative: bool, significand: &BitVec, exp: i64, format: FloatFormat) -> BitVec {
+	let mut ret = BitVec::new();
+	ret.push(is_negative);
+
+	let mant_bits = format.mant_bits as usize;
+	let min_exp = 1 - format.bias();
+	let mut unbiased = exp + significand.len() as i64 - 1;
+	if unbiased >= min_exp {
+		// normal, keep the leading one and mant_bits more
+		let mut padded = significand.clone();
+		while padded.len() < mant_bits + 1 {
+			padded.push(false);
+		}
+		let mut rounded = round_shift(&padded, padded.len() - (mant_bits + 1));
+		if rounded.len() > mant_bits + 1 {
+			rounded.pop();
+			unbiased += 1;
+		}
+		// E4M3 uses its top exponent for numbers, except for the nan pattern
+		let is_nan_pattern = format.kind == FloatKind::FiniteNan && unbiased == format.max_exp() && rounded.all();
+		if unbiased > format.max_exp() || is_nan_pattern {
+			return special(is_negative, false, format);
+		}
+		ret.extend(bits_of((unbiased + format.bias()) as u128, format.exp_bits));
+		ret.extend_from_bitslice(&rounded[1..]);
+	} else {
+		// subnormal, counting in steps of 2^(min_exp - mant_bits)
+		let shift = exp - (min_exp - mant_bits as i64);
+		let rounded = if shift >= 0 {
+			let mut shifted = significand.clone();
+			for _ in 0..shift {
+				shifted.push(false);
+			}
+			shifted
+		} else {
+			round_shift(significand, (-shift) as usize)
+		};
+		if rounded.len() > mant_bits {
+			// rounded up to the smallest normal number
+			ret.extend(bits_of(1, format.exp_bits));
+			ret.extend(bits_of(0, format.mant_bits));
+		} else {
+			ret.extend(bits_of(0, format.exp_bits));
+			ret.extend(bits_of(0, format.mant_bits - rounded.len() as u32));
+			ret.extend_from_bitslice(&rounded);
+		}
+	}
+	ret
+}
+
+/// Encodes the nonzero number significand * 2^exp (with no leading zeroes) as a posit<n, es>
+/// Posits never round to zero or NaR, so numbers out of range become the smallest or largest posit
+fn encode_posit(is_negative: bool, significand: &BitVec, exp: i64, n: u32, es: u32) -> BitVec {
+	let scale = exp + significand.len() as i64 - 1;
+	let k = scale.div_euclid(1 << es);
+	let e = scale.rem_euclid(1 << es);
+
+	let max_k = n as i64 - 2;
+	let mut body = if k > max_k {
+		bits_of(u128::MAX, n - 1)
+	} else if k < -max_k {
+		bits_of(1, n - 1)
+	} else {
+		// regime, exponent then fraction, rounded to fit after the sign bit
+		let mut body = BitVec::new();
+		if k >= 0 {
+			body.extend(bits_of(u128::MAX, k as u32 + 1));
+			body.push(false);
+		} else {
+			body.extend(bits_of(0, (-k) as u32));
+			body.push(true);
+		}
+		body.extend(bits_of(e as u128, es));
+		body.extend_from_bitslice(&significand[1..]);
+		while body.len() < n as usize - 1 {
+			body.push(false);
+		}
+
+		let rounded = round_shift(&body, body.len() - (n as usize - 1));
+		if rounded.len() > n as usize - 1 {
+			bits_of(u128::MAX, n - 1)
+		} else if !rounded.any() {
+			bits_of(1, n - 1)
+		} else {
+			rounded
+		}
+	};
+
+	body.insert(0, false);
+	if is_negative {
+		negative(&mut body);
+	}
+	body
+}
+
+/// Decodes the float bit pattern in bits, which must be as wide as the format
+pub fn decode(bits: &BitVec, format: FloatFormat) -> Decoded {
+	if let FloatKind::Posit(_) = format.kind {
+		return decode_posit(bits, format.exp_bits);
+	}
+
+	let is_negative = bits[0];
+	let exp_field = num_of(&bits[1..1 + format.exp_bits as usize]) as i64;
+	let mant = bits[1 + format.exp_bits as usize..].to_bitvec();
+	let all_ones = exp_field == (1i64 << format.exp_bits) - 1;
+
+	let decoded = |class, significand, exp| Decoded { is_negative, class, significand, exp };
+	match format.kind {
+		FloatKind::Ieee if all_ones => {
+			let class = if mant.any() { FloatClass::Nan } else { FloatClass::Infinite };
+			return decoded(class, BitVec::new(), 0);
+		}
+		FloatKind::FiniteNan if all_ones && mant.all() => {
+			return decoded(FloatClass::Nan, BitVec::new(), 0);
+		}
+		_ => { }
+	}
+
+	if exp_field == 0 {
+		if mant.any() {
+			decoded(FloatClass::Subnormal, mant, 1 - format.bias() - format.mant_bits as i64)
+		} else {
+			decoded(FloatClass::Zero, BitVec::new(), 0)
+		}
+	} else {
+		let mut significand = mant;
+		significand.insert(0, true);
+		decoded(FloatClass::Normal, significand, exp_field - format.bias() - format.mant_bits as i64)
+	}
+}
+
+/// Splits the bits after the sign bit of a positive posit into the regime value k, the exponent bits and the fraction bits
+/// Exponent bits cut off by a long regime are left out
+fn posit_fields(body: &BitVec, es: u32) -> (i64, BitVec, BitVec) {
+	let run_bit = body[0];
+	let run = body.iter().take_while(|b| **b == run_bit).count();
+	let k = if run_bit { run as i64 - 1 } else { -(run as i64) };
+
+	// skip the bit that ends the regime
+	let rest = if run + 1 < body.len() { body[run + 1..].to_bitvec() } else { BitVec::new() };
+	let exp_len = rest.len().min(es as usize);
+	(k, rest[..exp_len].to_bitvec(), rest[exp_len..].to_bitvec())
+}
+
+/// Gets the scale of a posit from its regime value and exponent bits, where missing exponent bits are zeroes
+fn posit_scale(k: i64, exponent: &BitVec, es: u32) -> i64 {
+	k * (1 << es) + (num_of(exponent) << (es as usize - exponent.len())) as i64
+}
+
+fn decode_posit(bits: &BitVec, es: u32) -> Decoded {
+	if !bits[1..].any() {
+		// zero or NaR
+		let class = if bits[0] { FloatClass::Nan } else { FloatClass::Zero };
+		return Decoded { is_negative: false, class, significand: BitVec::new(), exp: 0 };
+	}
+
+	let is_negative = bits[0];
+	let mut magnitude = bits.clone();
+	if is_negative {
+		negative(&mut magnitude);
+	}
+	let (k, exponent, fraction) = posit_fields(&magnitude[1..].to_bitvec(), es);
+
+	let exp = posit_scale(k, &exponent, es) - fraction.len() as i64;
+	let mut significand = fraction;
+	significand.insert(0, true);
+	Decoded { is_negative, class: FloatClass::Normal, significand, exp }
+}
+
+/// Parses a real number (with any '-' already removed) and encodes it in the format
+/// Accepts decimals such as "0.7071" or "1e5", C99 hexadecimal floating point literals such as "0x1.921fb6p+1", "inf" and "nan"
+pub fn read_float(positive_arg: &str, is_negative: bool, format: FloatFormat) -> Result<BitVec, String> {
+	match positive_arg {
+		"inf" | "infinity" => return Ok(special(is_negative, false, format)),
+		"nan" => return Ok(special(is_negative, true, format)),
+		_ => { }
+	}
+
+	if let Some(literal) = positive_arg.strip_prefix("0x") {
+		let (mantissa, exp) = match literal.split_once(['p', 'P']) {
+			Some(split) => split,
+			None => return Err(format!("Hexadecimal floats need a binary exponent ('p'): {}", positive_arg))
+		};
+		let exp = match exp.parse::<i64>() {
+			Ok(exp) => exp,
+			Err(_) => return Err(format!("Invalid exponent: {}", exp))
+		};
+		let (int, frac) = mantissa.split_once('.').unwrap_or((mantissa, ""));
+		if int.is_empty() && frac.is_empty() {
+			return Err(format!("Missing digits in {}", positive_arg));
+		}
+
+		// read all the digits as one integer, moving the radix point into the exponent
+		let digits = format!("{}{}", int, frac);
+		let significand = read(&digits, ReadMode::Hex, WriteMode::Binary, WriteLength::Unfixed, false)?;
+		return Ok(encode(is_negative, &significand, exp - 4 * frac.len() as i64, format));
+	}
+
+	// double the decimal until its integer part has more bits than the format keeps,
+	// or until it is below half the smallest number of the format
+	let (precision, max_shift) = match format.kind {
+		FloatKind::Posit(n) => (n as i64 + 2, ((n as i64 - 2) << format.exp_bits) + n as i64 + 2),
+		FloatKind::Ieee | FloatKind::FiniteNan => (format.mant_bits as i64 + 2, format.bias() + format.mant_bits as i64 + 2)
+	};
+	let target = Decimal::from_digits(false, "1").shift(precision);
+
+	// a decimal exponent moves the decimal point, and past max_shift places it only moves the number further out of range
+	let (mantissa, exp) = match positive_arg.split_once(['e', 'E']) {
+		Some((mantissa, exp)) => match exp.parse::<i64>() {
+			Ok(exp) => (mantissa, exp.clamp(-max_shift, max_shift)),
+			Err(_) => return Err(format!("Invalid exponent: {}", exp))
+		},
+		None => (positive_arg, 0)
+	};
+	let mut scaled = Decimal::parse(mantissa)?.move_point(exp);
+	let mut shift = 0;
+	while scaled.cmp_magnitude(&target).is_lt() && shift < max_shift {
+		scaled = scaled.mul_small(2);
+		shift += 1;
+	}
+
+	// the rest of the fraction only matters for rounding, so it becomes one sticky bit
+	let mut significand = read(&scaled.int_str(), ReadMode::Decimal, WriteMode::Binary, WriteLength::Unfixed, false)?;
+	significand.push(!scaled.frac().is_zero());
+	Ok(encode(is_negative, &significand, -shift - 1, format))
+}
+
+/// Gets the exact decimal value of a decoded float
+fn value_str(decoded: &Decoded, format: FloatFormat) -> String {
+	let sign = if decoded.is_negative { "-" } else { "" };
+	match decoded.class {
+		FloatClass::Nan if matches!(format.kind, FloatKind::Posit(_)) => "NaR".to_string(),
+		FloatClass::Nan => format!("{}nan", sign),
+		FloatClass::Infinite => format!("{}inf", sign),
+		FloatClass::Zero => format!("{}0", sign),
+		FloatClass::Normal | FloatClass::Subnormal => {
+			let int_str = write(&mut decoded.significand.clone(), WriteMode::Decimal, &WriteSeparator::None, false, false);
+			Decimal::from_digits(decoded.is_negative, &int_str).shift(decoded.exp).to_string()
+		}
+	}
+}
+
+/// Writes the float bit pattern in bits (which must be as wide as the format) in the canonical form of printf's %a
+pub fn write_hex_float(bits: &BitVec, format: FloatFormat) -> String {
+	let decoded = decode(bits, format);
+	let sign = if decoded.is_negative { "-" } else { "" };
+	let mut mant = match decoded.class {
+		FloatClass::Nan => return format!("{}nan", sign),
+		FloatClass::Infinite => return format!("{}inf", sign),
+		FloatClass::Zero => return format!("{}0x0p+0", sign),
+		FloatClass::Normal | FloatClass::Subnormal => decoded.significand
+	};
+
+	let (lead, exp) = if decoded.class == FloatClass::Subnormal && format.bias() >= F64.bias() {
+		// subnormal in a format at least as wide as a double, printed like printf does for double subnormals
+		('0', 1 - format.bias())
+	} else {
+		// everything else is normal as a double, so normalize it
+		let first = mant.first_one().unwrap();
+		mant = mant[first + 1..].to_bitvec();
+		('1', decoded.exp + mant.len() as i64)
+	};
+
+	// pad the mantissa to whole hex digits and drop trailing zero digits
+	while mant.len() % 4 != 0 {
+		mant.push(false);
+	}
+	let mut digits: String = mant.chunks(4).map(|nibble| char::from_digit(num_of(nibble) as u32, 16).unwrap()).collect();
+	while digits.ends_with('0') {
+		digits.pop();
+	}
+
+	let point = if digits.is_empty() { "" } else { "." };
+	format!("{}0x{}{}{}p{:+}", sign, lead, point, digits, exp)
+}
+
+/// Writes the value of the float bit pattern in bits (which must be as wide as the format) and a breakdown of its fields
+pub fn write_fields(bits: &BitVec, format: FloatFormat) -> String {
+	let decoded = decode(bits, format);
+	let bin = |bits: &BitVec| -> String { bits.iter().map(|b| if *b { '1' } else { '0' }).collect() };
+	let class = match decoded.class {
+		FloatClass::Zero => "zero",
+		FloatClass::Subnormal => "subnormal",
+		FloatClass::Normal => "normal",
+		FloatClass::Infinite => "infinite",
+		FloatClass::Nan if matches!(format.kind, FloatKind::Posit(_)) => "nar",
+		FloatClass::Nan => "nan"
+	};
+	let mut ret = format!("value={} class={} sign={}", value_str(&decoded, format), class, bits[0] as u8);
+
+	match format.kind {
+		FloatKind::Posit(_) => {
+			if decoded.class == FloatClass::Normal {
+				// the fields of negative posits are read from their two's complement
+				let mut magnitude = bits.clone();
+				if bits[0] {
+					negative(&mut magnitude);
+				}
+				let (k, exponent, fraction) = posit_fields(&magnitude[1..].to_bitvec(), format.exp_bits);
+				let regime_len = bits.len() - 1 - exponent.len() - fraction.len();
+				ret.push_str(&format!(" regime=0b{} (k={}) exponent=0b{} (2^{}) fraction=0b{}",
+					bin(&magnitude[1..1 + regime_len].to_bitvec()),
+					k,
+					bin(&exponent),
+					posit_scale(k, &exponent, format.exp_bits),
+					bin(&fraction)));
+			}
+		}
+		FloatKind::Ieee | FloatKind::FiniteNan => {
+			let exponent = bits[1..1 + format.exp_bits as usize].to_bitvec();
+			ret.push_str(&format!(" exponent=0b{}", bin(&exponent)));
+			match decoded.class {
+				FloatClass::Normal => ret.push_str(&format!(" (2^{})", num_of(&exponent) as i64 - format.bias())),
+				FloatClass::Subnormal => ret.push_str(&format!(" (2^{})", 1 - format.bias())),
+				FloatClass::Zero | FloatClass::Infinite | FloatClass::Nan => { }
+			}
+			ret.push_str(&format!(" mantissa=0b{}", bin(&bits[1 + format.exp_bits as usize..].to_bitvec())));
+		}
+	}
+	ret
+}
+
+#[cfg(test)]
+mod tests {
+	use crate::float::*;
+
+	fn read_num(arg: &str, format: FloatFormat) -> u64 {
+		match arg.strip_prefix('-') {
+			Some(arg) => num_of(&read_float(arg, true, format).unwrap()) as u64,
+			None => num_of(&read_float(arg, false, format).unwrap()) as u64
+		}
+	}
+
+	fn read64(arg: &str) -> u64 {
+		read_num(arg, F64)
+	}
+
+	fn read32(arg: &str) -> u64 {
+		read_num(arg, F32)
+	}
+
+	fn fields(num: u64, format: FloatFormat) -> String {
+		write_fields(&bits_of(num as u128, format.width() as u32), format)
+	}
+
+	#[test]
+	fn read_hex_float_tests() {
+		assert_eq!(read64("0x1.921fb54442d18p+1"), std::f64::consts::PI.to_bits());
+		assert_eq!(read32("0x1.921fb6p+1"), std::f32::consts::PI.to_bits() as u64);
+		assert_eq!(read64("0x1p0"), 1.0f64.to_bits());
+		assert_eq!(read64("-0x.8p1"), (-1.0f64).to_bits());
+		assert_eq!(read64("0x10p-4"), 1.0f64.to_bits());
+		assert_eq!(read64("0x0p0"), 0);
+		assert_eq!(read64("0x1p-1074"), 1);
+		assert_eq!(read64("0x1p1024"), f64::INFINITY.to_bits());
+		assert_eq!(read64("inf"), f64::INFINITY.to_bits());
+		assert_eq!(read32("0x1p-149"), 1);
+		assert_eq!(read32("0x1p-150"), 0);
+		assert_eq!(read32("0x1.8p-149"), 2);
+		assert_eq!(read32("0x1.fffffffp0"), 2.0f32.to_bits() as u64);
+		assert_eq!(read32("0x1.fffffcp-127"), 0x007FFFFF);
+		assert_eq!(read32("0x1.fffffep-127"), 0x00800000);
+		assert!(read_float("0x1.8", false, F64).is_err());
+		assert!(read_float("1.8p3", false, F64).is_err());
+	}
+
+	#[test]
+	fn read_decimal_float_tests() {
+		assert_eq!(read64("0.1"), 0.1f64.to_bits());
+		assert_eq!(read64("-2.5"), (-2.5f64).to_bits());
+		assert_eq!(read32("2.718281828459045"), std::f32::consts::E.to_bits() as u64);
+		assert_eq!(read32("16777217"), 16777216f32.to_bits() as u64);
+		assert_eq!(read64(&format!("0.{}1", "0".repeat(400))), 0);
+		assert_eq!(read_num("1", BF16), 0x3F80);
+		assert_eq!(read_num("65504", F16), 0x7BFF);
+		assert_eq!(read_num("65520", F16), 0x7C00);
+		assert_eq!(read_num("1", TF32), 0x1FC00);
+		assert_eq!(read_num("1e5", F16), 0x7C00);
+		assert_eq!(read_num("6.5504E4", F16), 0x7BFF);
+		assert_eq!(read_num("1.5e-1", F32), 0.15f32.to_bits() as u64);
+		assert_eq!(read_num("1e400", F64), f64::INFINITY.to_bits());
+		assert_eq!(read_num("1e-400", F64), 0);
+		assert!(read_float("1e", false, F16).is_err());
+	}
+
+	#[test]
+	fn write_hex_float_tests() {
+		assert_eq!(write_hex_float(&bits_of(std::f64::consts::PI.to_bits() as u128, 64), F64), "0x1.921fb54442d18p+1");
+		assert_eq!(write_hex_float(&bits_of(std::f32::consts::PI.to_bits() as u128, 32), F32), "0x1.921fb6p+1");
+		assert_eq!(write_hex_float(&bits_of(1.0f64.to_bits() as u128, 64), F64), "0x1p+0");
+		assert_eq!(write_hex_float(&bits_of((-0.75f32).to_bits() as u128, 32), F32), "-0x1.8p-1");
+		assert_eq!(write_hex_float(&bits_of(1, 64), F64), "0x0.0000000000001p-1022");
+		assert_eq!(write_hex_float(&bits_of(1, 32), F32), "0x1p-149");
+		assert_eq!(write_hex_float(&bits_of(3, 32), F32), "0x1.8p-148");
+		assert_eq!(write_hex_float(&bits_of(0x80000000, 32), F32), "-0x0p+0");
+		assert_eq!(write_hex_float(&bits_of(f64::INFINITY.to_bits() as u128, 64), F64), "inf");
+		assert_eq!(write_hex_float(&bits_of(f32::NAN.to_bits() as u128, 32), F32), "nan");
+	}
+
+	#[test]
+	fn fp8_tests() {
+		assert_eq!(read_num("448", E4M3), 0x7E);
+		assert_eq!(read_num("480", E4M3), 0x7F);
+		assert_eq!(read_num("-1000", E4M3), 0xFF);
+		assert_eq!(read_num("inf", E4M3), 0x7F);
+		assert_eq!(read_num("1000", FloatFormat::parse("e4m3:sat").unwrap()), 0x7E);
+		assert_eq!(read_num("0.001953125", E4M3), 0x01);
+		assert_eq!(read_num("57344", E5M2), 0x7B);
+		assert_eq!(read_num("65536", E5M2), 0x7C);
+		assert_eq!(read_num("65536", FloatFormat::parse("e5m2:sat").unwrap()), 0x7B);
+
+		assert_eq!(fields(0x7E, E4M3), "value=448 class=normal sign=0 exponent=0b1111 (2^8) mantissa=0b110");
+		assert_eq!(fields(0x7F, E4M3), "value=nan class=nan sign=0 exponent=0b1111 mantissa=0b111");
+		assert_eq!(fields(0x7C, E5M2), "value=inf class=infinite sign=0 exponent=0b11111 mantissa=0b00");
+		assert_eq!(fields(0x01, E5M2), "value=0.0000152587890625 class=subnormal sign=0 exponent=0b00000 (2^-14) mantissa=0b01");
+	}
+
+	#[test]
+	fn fields_tests() {
+		assert_eq!(fields(0x3F80, BF16), "value=1 class=normal sign=0 exponent=0b01111111 (2^0) mantissa=0b0000000");
+		assert_eq!(fields(0xC000, F16), "value=-2 class=normal sign=1 exponent=0b10000 (2^1) mantissa=0b0000000000");
+		assert_eq!(fields(0x8000, F16), "value=-0 class=zero sign=1 exponent=0b00000 mantissa=0b0000000000");
+	}
+
+	#[test]
+	fn posit_tests() {
+		let p16 = FloatFormat::parse("posit<16,1>").unwrap();
+		assert_eq!(read_num("1", p16), 0x4000);
+		assert_eq!(read_num("-1", p16), 0xC000);
+		assert_eq!(read_num("0", p16), 0x0000);
+		assert_eq!(read_num("nan", p16), 0x8000);
+		assert_eq!(read_num("1000000000000", p16), 0x7FFF);
+		assert_eq!(read_num("0.000000000001", p16), 0x0001);
+		assert_eq!(read_num("0.75", FloatFormat::parse("posit8,0").unwrap()), 0x30);
+
+		assert_eq!(fields(0x4000, p16), "value=1 class=normal sign=0 regime=0b10 (k=0) exponent=0b0 (2^0) fraction=0b000000000000");
+		assert_eq!(fields(0x7FFF, p16), "value=268435456 class=normal sign=0 regime=0b111111111111111 (k=14) exponent=0b (2^28) fraction=0b");
+		assert_eq!(fields(0xC000, p16), "value=-1 class=normal sign=1 regime=0b10 (k=0) exponent=0b0 (2^0) fraction=0b000000000000");
+		assert_eq!(fields(0x8000, p16), "value=NaR class=nar sign=1");
+		assert_eq!(write_hex_float(&bits_of(0x0001, 16), p16), "0x1p-28");
+		assert!(FloatFormat::parse("posit<1,0>").is_err());
+	}
+}
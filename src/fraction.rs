@@ -0,0 +1,74 @@
+use std::collections::HashMap;
+
+use crate::decimal::Decimal;
+
+/// Converts the digits after the radix point of a number in base from_base into digits of base to_base
+/// Digits repeating forever are put in parentheses, e.g. 0.1 in decimal becomes "0(0011)" in binary
+/// If neither the end of the digits nor the repeating part is found within max_digits, "..." is added
+pub fn convert_fraction(digits: &str, from_base: u32, to_base: u32, upper: bool, max_digits: usize) -> Result<String, String> {
+	// the fraction is numerator / denominator, where the denominator is a power of from_base
+	let mut numerator = Decimal::from_digits(false, "0");
+	let mut denominator = Decimal::from_digits(false, "1");
+	for c in digits.chars() {
+		let digit = match c.to_digit(from_base) {
+			Some(digit) => digit,
+			None => return Err(format!("Character {} not allowed in base {} fractions", c, from_base))
+		};
+		numerator = numerator.mul_small(from_base).add(&Decimal::from_digits(false, &digit.to_string()));
+		denominator = denominator.mul_small(from_base);
+	}
+
+	// long division, remembering where each numerator was seen to find where the digits start repeating
+	let mut ret = Vec::new();
+	let mut seen = HashMap::new();
+	while !numerator.is_zero() {
+		if let Some(start) = seen.get(&numerator) {
+			let mut repeating: String = ret[..*start].iter().collect();
+			repeating.push('(');
+			repeating.extend(ret[*start..].iter());
+			repeating.push(')');
+			return Ok(repeating);
+		}
+		if ret.len() == max_digits {
+			let mut truncated: String = ret.iter().collect();
+			truncated.push_str("...");
+			return Ok(truncated);
+		}
+		seen.insert(numerator.clone(), ret.len());
+
+		numerator = numerator.mul_small(to_base);
+		let mut digit = 0;
+		while numerator.cmp_magnitude(&denominator).is_ge() {
+			numerator = numerator.sub(&denominator);
+			digit += 1;
+		}
+		let c = char::from_digit(digit, to_base).unwrap();
+		ret.push(if upper { c.to_ascii_uppercase() } else { c });
+	}
+	Ok(ret.iter().collect())
+}
+
+#[cfg(test)]
+mod tests {
+	use crate::fraction::*;
+
+	#[test]
+	fn exact_fraction_tests() {
+		assert_eq!(convert_fraction("5", 10, 2, true, 64), Ok("1".to_string()));
+		assert_eq!(convert_fraction("8", 16, 10, true, 64), Ok("5".to_string()));
+		assert_eq!(convert_fraction("001", 2, 10, true, 64), Ok("125".to_string()));
+		assert_eq!(convert_fraction("1", 2, 16, true, 64), Ok("8".to_string()));
+		assert_eq!(convert_fraction("0", 10, 16, true, 64), Ok("".to_string()));
+		assert_eq!(convert_fraction("abc", 16, 16, false, 64), Ok("abc".to_string()));
+		assert!(convert_fraction("2", 2, 10, true, 64).is_err());
+	}
+
+	#[test]
+	fn repeating_fraction_tests() {
+		assert_eq!(convert_fraction("1", 10, 2, true, 64), Ok("0(0011)".to_string()));
+		assert_eq!(convert_fraction("1", 10, 16, true, 64), Ok("1(9)".to_string()));
+		assert_eq!(convert_fraction("2", 10, 8, true, 64), Ok("(1463)".to_string()));
+		assert_eq!(convert_fraction("1", 10, 2, true, 3), Ok("000...".to_string()));
+		assert_eq!(convert_fraction("1", 10, 2, true, 5), Ok("0(0011)".to_string()));
+	}
+}
@@ -0,0 +1,196 @@
+use bitvec::prelude::BitVec;
+use crate::{bytes::{bits_of, hex_bytes}, varint::to_i128};
+
+/// A self-describing binary format whose integer items start with a header byte
+#[derive(PartialEq, Eq, Debug)]
+#[derive(Clone, Copy)]
+pub enum ItemFormat {
+	/// RFC 8949 major type 0 (unsigned) and 1 (negative) items
+	Cbor,
+	/// fixint, uint 8-64 and int 8-64 items
+	MessagePack
+}
+
+impl ItemFormat {
+	pub fn parse(param: &str) -> Result<ItemFormat, String> {
+		match param {
+			"cbor" => Ok(ItemFormat::Cbor),
+			"msgpack" | "messagepack" => Ok(ItemFormat::MessagePack),
+			_ => Err(format!("Unknown item format: {}", param))
+		}
+	}
+
+	fn name(&self) -> &str {
+		match self {
+			ItemFormat::Cbor => "CBOR",
+			ItemFormat::MessagePack => "MessagePack"
+		}
+	}
+}
+
+/// Encodes the value of the bits (signed if signed_mode) as the shortest item
+pub fn encode(bits: &BitVec, signed_mode: bool, format: ItemFormat) -> Result<Vec<u8>, String> {
+	let value = match to_i128(bits, signed_mode) {
+		Some(value) if value >= -(1i128 << 64) && value <= u64::MAX as i128 => value,
+		_ => return Err(format!("{} integers are at most 64 bits", format.name()))
+	};
+	let mut bytes = Vec::new();
+	match format {
+		ItemFormat::Cbor => {
+			// negative numbers are major type 1 with the argument -1-value
+			let (major, argument) = if value < 0 { (1u8, (-1 - value) as u64) } else { (0u8, value as u64) };
+			let (info, len) = match argument {
+				0..=23 => (argument as u8, 0),
+				24..=0xFF => (24, 1),
+				0x100..=0xFFFF => (25, 2),
+				0x1_0000..=0xFFFF_FFFF => (26, 4),
+				_ => (27, 8)
+			};
+			bytes.push((major << 5) | info);
+			bytes.extend(&argument.to_be_bytes()[8 - len..]);
+		}
+		ItemFormat::MessagePack => {
+			if value < -(1i128 << 63) {
+				return Err("MessagePack integers are at least -2^63".to_string());
+			}
+			let (header, len) = match value {
+				// positive and negative fixints
+				-32..=0x7F => (value as u8, 0),
+				0x80..=0xFF => (0xCC, 1),
+				0x100..=0xFFFF => (0xCD, 2),
+				0x1_0000..=0xFFFF_FFFF => (0xCE, 4),
+				0x1_0000_0000.. => (0xCF, 8),
+				-0x80..=-33 => (0xD0, 1),
+				-0x8000..=-0x81 => (0xD1, 2),
+				-0x8000_0000..=-0x8001 => (0xD2, 4),
+				_ => (0xD3, 8)
+			};
+			bytes.push(header);
+			bytes.extend(&(value as u64).to_be_bytes()[8 - len..]);
+		}
+	}
+	Ok(bytes)
+}
+
+/// An integer item split into its fields
+struct Item {
+	value: i128,
+	header: u8,
+	argument_len: usize,
+	/// a description of the header byte
+	kind: String
+}
+
+fn parse_item(bytes: &[u8], format: ItemFormat) -> Result<Item, String> {
+	let header = bytes[0];
+	let (argument_len, signed, kind) = match format {
+		ItemFormat::Cbor => {
+			let major = header >> 5;
+			let info = header & 0x1F;
+			let major_name = match major {
+				0 => "unsigned integer",
+				1 => "negative integer",
+				_ => return Err(format!("CBOR major type {} is not an integer", major))
+			};
+			let argument_len = match info {
+				0..=23 => 0,
+				24..=27 => 1 << (info - 24),
+				_ => return Err(format!("CBOR additional information {} is not an integer length", info))
+			};
+			let info_name = if argument_len == 0 { "value in header".to_string() } else { format!("{} byte argument", argument_len) };
+			(argument_len, false, format!("major={} ({}) additional={} ({})", major, major_name, info, info_name))
+		}
+		ItemFormat::MessagePack => {
+			let (argument_len, signed, name) = match header {
+				0x00..=0x7F => (0, false, "positive fixint".to_string()),
+				0xE0..=0xFF => (0, true, "negative fixint".to_string()),
+				0xCC..=0xCF => (1 << (header - 0xCC), false, format!("uint {}", 8 << (header - 0xCC))),
+				0xD0..=0xD3 => (1 << (header - 0xD0), true, format!("int {}", 8 << (header - 0xD0))),
+				_ => return Err(format!("MessagePack type 0x{:02X} is not an integer", header))
+			};
+			(argument_len, signed, format!("({})", name))
+		}
+	};
+	if bytes.len() != 1 + argument_len {
+		return Err(format!("Integer item has {} bytes but its header says {}", bytes.len(), 1 + argument_len));
+	}
+	let mut value = if argument_len == 0 {
+		match format {
+			ItemFormat::Cbor => (header & 0x1F) as i128,
+			ItemFormat::MessagePack => header as i8 as i128
+		}
+	} else {
+		bytes[1..].iter().fold(0i128, |n, b| (n << 8) | *b as i128)
+	};
+	if signed && argument_len > 0 && bytes[1] & 0x80 != 0 {
+		value -= 1i128 << (argument_len * 8);
+	}
+	if format == ItemFormat::Cbor && header >> 5 == 1 {
+		value = -1 - value;
+	}
+	Ok(Item { value, header, argument_len, kind })
+}
+
+/// Decodes the item written in hexadecimal in arg
+/// Returns whether the number is negative and the bits of its magnitude
+pub fn decode(arg: &str, format: ItemFormat) -> Result<(bool, BitVec), String> {
+	let item = parse_item(&hex_bytes(arg)?, format)?;
+	let magnitude = item.value.unsigned_abs();
+	Ok((item.value < 0, bits_of(magnitude, 128)))
+}
+
+/// Describes the fields of an encoded item, e.g. 'header=0x19 major=0 (unsigned integer) additional=25 (2 byte argument) argument=0x03E8 value=1000'
+pub fn write_fields(bytes: &[u8], format: ItemFormat) -> String {
+	let item = parse_item(bytes, format).unwrap();
+	let mut ret = format!("header=0x{:02X} {}", item.header, item.kind);
+	if item.argument_len > 0 {
+		let argument: String = bytes[1..].iter().map(|b| format!("{:02X}", b)).collect();
+		ret.push_str(&format!(" argument=0x{}", argument));
+	}
+	ret.push_str(&format!(" value={}", item.value));
+	ret
+}
+
+#[cfg(test)]
+mod tests {
+	use crate::bytes::num_of;
+	use crate::item::*;
+
+	fn value_of(decoded: (bool, BitVec)) -> i128 {
+		let magnitude = num_of(&decoded.1) as i128;
+		if decoded.0 { -magnitude } else { magnitude }
+	}
+
+	#[test]
+	fn cbor_tests() {
+		assert_eq!(encode(&bits_of(10, 8), false, ItemFormat::Cbor), Ok(vec![0x0A]));
+		assert_eq!(encode(&bits_of(24, 8), false, ItemFormat::Cbor), Ok(vec![0x18, 0x18]));
+		assert_eq!(encode(&bits_of(1000, 16), false, ItemFormat::Cbor), Ok(vec![0x19, 0x03, 0xE8]));
+		assert_eq!(encode(&bits_of(-1i128 as u128, 8), true, ItemFormat::Cbor), Ok(vec![0x20]));
+		assert_eq!(encode(&bits_of(-1000i128 as u128, 16), true, ItemFormat::Cbor), Ok(vec![0x39, 0x03, 0xE7]));
+		assert_eq!(encode(&bits_of(1 << 40, 64), false, ItemFormat::Cbor), Ok(vec![0x1B, 0, 0, 1, 0, 0, 0, 0, 0]));
+		assert_eq!(decode("1903E8", ItemFormat::Cbor).map(value_of), Ok(1000));
+		assert_eq!(decode("3903E7", ItemFormat::Cbor).map(value_of), Ok(-1000));
+		assert_eq!(decode("3BFFFFFFFFFFFFFFFF", ItemFormat::Cbor).map(value_of), Ok(-(1i128 << 64)));
+		assert!(decode("1903", ItemFormat::Cbor).is_err());
+		assert!(decode("40", ItemFormat::Cbor).is_err());
+		assert!(decode("1C", ItemFormat::Cbor).is_err());
+		assert_eq!(write_fields(&[0x19, 0x03, 0xE8], ItemFormat::Cbor), "header=0x19 major=0 (unsigned integer) additional=25 (2 byte argument) argument=0x03E8 value=1000");
+		assert_eq!(write_fields(&[0x20], ItemFormat::Cbor), "header=0x20 major=1 (negative integer) additional=0 (value in header) value=-1");
+	}
+
+	#[test]
+	fn msgpack_tests() {
+		assert_eq!(encode(&bits_of(127, 8), false, ItemFormat::MessagePack), Ok(vec![0x7F]));
+		assert_eq!(encode(&bits_of(128, 16), false, ItemFormat::MessagePack), Ok(vec![0xCC, 0x80]));
+		assert_eq!(encode(&bits_of(-32i128 as u128, 8), true, ItemFormat::MessagePack), Ok(vec![0xE0]));
+		assert_eq!(encode(&bits_of(-33i128 as u128, 8), true, ItemFormat::MessagePack), Ok(vec![0xD0, 0xDF]));
+		assert_eq!(encode(&bits_of(-1000i128 as u128, 16), true, ItemFormat::MessagePack), Ok(vec![0xD1, 0xFC, 0x18]));
+		assert_eq!(encode(&bits_of(70000, 32), false, ItemFormat::MessagePack), Ok(vec![0xCE, 0x00, 0x01, 0x11, 0x70]));
+		assert_eq!(decode("D1FC18", ItemFormat::MessagePack).map(value_of), Ok(-1000));
+		assert_eq!(decode("CFFFFFFFFFFFFFFFFF", ItemFormat::MessagePack).map(value_of), Ok(u64::MAX as i128));
+		assert_eq!(decode("FF", ItemFormat::MessagePack).map(value_of), Ok(-1));
+		assert!(decode("C0", ItemFormat::MessagePack).is_err());
+		assert_eq!(write_fields(&[0xD0, 0xDF], ItemFormat::MessagePack), "header=0xD0 (int 8) argument=0xDF value=-33");
+	}
+}
@@ -2,6 +2,40 @@ use std::{io, ops::BitXorAssign, process::exit, vec::Vec};
 
 use queues::{CircularBuffer, IsQueue};
 use bitvec::prelude::BitVec;
+use baseenc::BaseEncoding;
+use bcd::BcdKind;
+use dfp::DecFormat;
+use dump::DumpFormat;
+use fixed::{QFormat, Rounding};
+use radix::Radix;
+use varint::Varint;
+use roman::RomanStyle;
+use float::FloatFormat;
+use item::ItemFormat;
+use text::{Encoding, TextFormat};
+use transform::{BitRange, Transform};
+use unicode::Utf;
+
+mod baseenc;
+mod bcd;
+mod bytes;
+mod decimal;
+mod der;
+mod dfp;
+mod dump;
+mod fixed;
+mod float;
+mod fraction;
+mod item;
+mod protobuf;
+mod radix;
+mod roman;
+mod stats;
+mod text;
+mod transform;
+mod unicode;
+mod unicode_data;
+mod varint;
 
 #[derive(PartialEq, Eq)]
 #[derive(Clone, Copy)]
@@ -10,7 +44,22 @@ enum ReadMode {
 	Decimal,
 	Hex,
 	Octal,
-	Interpret
+	Interpret,
+	FixedPoint(QFormat),
+	Float(FloatFormat),
+	Dfp(DecFormat),
+	Bcd(BcdKind),
+	Radix(Radix),
+	Roman(bool /* lenient */),
+	BaseEncoded(BaseEncoding),
+	Varint(Varint),
+	Item(ItemFormat),
+	DerInteger,
+	Oid,
+	CodePoint,
+	Utf(Utf),
+	Text(Encoding),
+	CharLiteral
 }
 
 #[derive(PartialEq, Eq)]
@@ -19,7 +68,73 @@ enum WriteMode {
 	Binary,
 	Decimal,
 	Hex(bool /* is uppercase */),
-	Octal
+	Octal,
+	Stats,
+	FixedPoint(QFormat),
+	HexFloat(FloatFormat),
+	FloatFields(FloatFormat),
+	Dfp(DecFormat),
+	DfpFields(DecFormat),
+	Bcd(BcdKind),
+	Radix(Radix),
+	Roman(RomanStyle),
+	BaseEncoded(BaseEncoding),
+	Varint(Varint),
+	Item(ItemFormat),
+	ItemFields(ItemFormat),
+	DerInteger,
+	Oid,
+	Protobuf,
+	CodePoint,
+	Utf(Utf),
+	UtfFields(Utf),
+	Text(TextFormat),
+	CharLiteral
+}
+
+impl WriteMode {
+	/// Gets if the mode writes a sequence of whole bytes, so unfixed widths are rounded up to bytes
+	fn is_byte_sequence(&self) -> bool {
+		match self {
+			WriteMode::BaseEncoded(_) | WriteMode::Oid | WriteMode::Protobuf | WriteMode::Text(_) | WriteMode::CharLiteral => true,
+			WriteMode::Binary | WriteMode::Decimal | WriteMode::Hex(_) | WriteMode::Octal | WriteMode::Stats | WriteMode::FixedPoint(_) | WriteMode::HexFloat(_) | WriteMode::FloatFields(_) | WriteMode::Dfp(_) | WriteMode::DfpFields(_) | WriteMode::Bcd(_) | WriteMode::Radix(_) | WriteMode::Roman(_) | WriteMode::Varint(_) | WriteMode::Item(_) | WriteMode::ItemFields(_) | WriteMode::DerInteger | WriteMode::CodePoint | WriteMode::Utf(_) | WriteMode::UtfFields(_) => false
+		}
+	}
+
+	/// Gets if the mode cannot write every number, so it must be written with try_write
+	fn is_fallible(&self) -> bool {
+		match self {
+			WriteMode::Bcd(_) | WriteMode::Radix(_) | WriteMode::Roman(_) | WriteMode::Varint(_) | WriteMode::Item(_) | WriteMode::ItemFields(_) | WriteMode::Protobuf | WriteMode::Oid | WriteMode::CodePoint | WriteMode::Utf(_) | WriteMode::UtfFields(_) => true,
+			WriteMode::Binary | WriteMode::Decimal | WriteMode::Hex(_) | WriteMode::Octal | WriteMode::Stats | WriteMode::FixedPoint(_) | WriteMode::HexFloat(_) | WriteMode::FloatFields(_) | WriteMode::Dfp(_) | WriteMode::DfpFields(_) | WriteMode::BaseEncoded(_) | WriteMode::DerInteger | WriteMode::Text(_) | WriteMode::CharLiteral => false
+		}
+	}
+
+	/// Gets the separator -c puts between groups, a space between groups of hexadecimal or bits and a comma otherwise
+	fn default_separator(&self) -> char {
+		match self {
+			WriteMode::Binary | WriteMode::Octal | WriteMode::Hex(_) | WriteMode::Bcd(_) | WriteMode::Varint(_) | WriteMode::DerInteger | WriteMode::Item(_) | WriteMode::ItemFields(_) | WriteMode::Utf(_) | WriteMode::UtfFields(_) => ' ',
+			WriteMode::Decimal | WriteMode::Stats | WriteMode::FixedPoint(_) | WriteMode::HexFloat(_) | WriteMode::FloatFields(_) | WriteMode::Dfp(_) | WriteMode::DfpFields(_) | WriteMode::Radix(_) | WriteMode::Roman(_) | WriteMode::BaseEncoded(_) | WriteMode::Oid | WriteMode::Protobuf | WriteMode::CodePoint | WriteMode::Text(_) | WriteMode::CharLiteral => ','
+		}
+	}
+
+	/// Gets the base of the modes that write positional digits, which are the only ones that can write fractions
+	fn base(&self) -> Option<u32> {
+		match self {
+			WriteMode::Binary => Some(2),
+			WriteMode::Octal => Some(8),
+			WriteMode::Decimal => Some(10),
+			WriteMode::Hex(_) => Some(16),
+			_ => None
+		}
+	}
+
+	/// Gets the bits in each unit of a fixed width, where octal widths count pairs of digits and all others count bytes
+	fn width_unit(&self) -> u64 {
+		match self {
+			WriteMode::Octal => 6,
+			_ => 8
+		}
+	}
 }
 
 #[derive(Clone, Copy)]
@@ -35,7 +150,37 @@ enum WriteSeparator {
 	None
 }
 
+/// The options that apply to every number converted
+struct Settings {
+	read_mode: ReadMode,
+	write_mode: WriteMode,
+	write_length: WriteLength,
+	write_separator: WriteSeparator,
+	signed_mode: bool,
+	write_prefix: bool,
+	/// operations applied in order to the bits of every number
+	transforms: Vec<Transform>,
+	/// the most digits written after the radix point of a fraction
+	max_frac_digits: usize
+}
+
+impl Default for Settings {
+	fn default() -> Settings {
+		Settings {
+			read_mode: ReadMode::Interpret,
+			write_mode: WriteMode::Hex(true),
+			write_length: WriteLength::Unfixed,
+			write_separator: WriteSeparator::None,
+			signed_mode: false,
+			write_prefix: true,
+			transforms: Vec::new(),
+			max_frac_digits: 64
+		}
+	}
+}
+
 /// Prints the program help
+#[allow(clippy::println_empty_string)]
 fn print_help() {
 	println!("HEX");
     println!("Tool for converting between different number types");
@@ -50,6 +195,9 @@ fn print_help() {
     println!("-O Forces the program to read input as an octal");
     println!("-X Forces the program to read input as a hexadecimal");
     println!("-F Lets the program decide how to read input based off prefix (default)");
+	println!("	Character literals are read as their value, e.g. \"'A'\" gives 0x41 and \"b'\\x7f'\" gives 0x7F, with C, Rust and Python escapes");
+	println!("	Multi-character constants are packed big-endian as C compilers do, e.g. the FourCC \"'RIFF'\" gives 0x52494646, where non-ASCII characters give their UTF-8 bytes");
+	println!("-S Reads input as text with C, Rust or Python escapes and writes its UTF-8 bytes, e.g. '-n -S Hello\\n' gives 48 65 6C 6C 6F 0A");
     println!("-b Writes output in binary with prefix");
     println!("-d Writes output in base 10 (decimal)");
     println!("-o Writes output in octal with prefix");
@@ -70,6 +218,119 @@ fn print_help() {
 	println!("-t Removes the separator character");
 	println!("-p Write prefixes on all non-decimal numbers (default)");
 	println!("-n Omit prefixes from all numbers");
+	println!("--stats Writes a report of popcount, leading/trailing zeroes, highest/lowest set bit, parity and powers of two");
+	println!("	Leading zeroes are counted up to the width set by -w");
+	println!("--q <m.n> Reads real numbers and encodes them as Qm.n fixed-point, e.g. '--q 1.15 0.7071' gives 0x5A82");
+	println!("	m counts the integer bits including the sign bit. Use -s for signed Qm.n, or -u for unsigned UQm.n");
+	println!("--qreal <m.n> Writes the real value of numbers read as Qm.n fixed-point");
+	println!("--qround <mode> Sets how --q rounds: nearest (default), trunc (toward zero) or floor");
+	println!("--qerr Reports the quantisation error of --q after every number");
+	println!("--float <fmt> Reads real numbers such as '0.7071', '1e5', '0x1.921fb6p+1', 'inf' or 'nan' into the bit pattern of a float");
+	println!("	fmt is one of: f16, bf16, tf32, f32, f64, e4m3, e5m2 or posit<N,es>. Add ':sat' to saturate instead of overflowing");
+	println!("	Hexadecimal float literals are read as f64 by default");
+	println!("--hexfloat <fmt> Writes float bit patterns as hexadecimal float literals, like printf's %a");
+	println!("--fields <fmt> Writes the exact value of float bit patterns and a breakdown of their fields");
+	println!("--dfp <fmt> Reads decimal numbers such as '123.45', '1.5e-3', 'inf' or 'nan' into the bit pattern of a decimal float");
+	println!("	fmt is one of: decimal32, decimal64, decimal128, followed by ':bid' (default) or ':dpd' for the encoding");
+	println!("	The exponent is kept as written where possible, so '1.50' and '1.5' give different bit patterns");
+	println!("--dfpreal <fmt> Writes the value of decimal float bit patterns");
+	println!("--dfpfields <fmt> Writes the value of decimal float bit patterns and a breakdown of their fields");
+	println!("--bcd <kind> Reads binary coded decimal written in hexadecimal, e.g. '--bcd packed 0x1234' gives 1234");
+	println!("	kind is one of: packed (two digits per byte), unpacked (one digit per byte) or zoned (EBCDIC, 0xF1F2F3C4)");
+	println!("	Sign nibbles 0xB and 0xD are negative, and need signed mode (-s)");
+	println!("--tobcd <kind> Writes numbers as binary coded decimal in hexadecimal, e.g. '--tobcd packed 1234' gives 0x1234");
+	println!("	In signed mode (-s) packed numbers get a trailing sign nibble (0xC or 0xD) and zoned numbers a sign zone");
+	println!("	Unpacked numbers have no sign, so they cannot be negative");
+	println!("--radix <system> Reads numbers in a number system that needs no sign for negative numbers");
+	println!("	system is one of: negabinary (base -2), negadecimal (base -10) or balanced-ternary (digits T01 or -0+)");
+	println!("	Negative numbers need signed mode (-s), e.g. '-s -d --radix negabinary 11' gives -1");
+	println!("	or one of: factoradic (digits separated by ':' once they pass 9), perm<n> (a permutation of 0..n-1, for n up to 1000, numbered by its factoradic Lehmer code) or zeckendorf (Fibonacci bits)");
+	println!("	e.g. '-d --toradix factoradic 463' gives 341010 and '-d --toradix perm6 463' gives 351042");
+	println!("	or bijective<k> (digits 1 to k, written 1-9 then A-Z) or columns (spreadsheet columns, A is 1 and XFD is 16384)");
+	println!("--toradix <system> Writes numbers in a number system that needs no sign for negative numbers");
+	println!("	Use balanced-ternary-signs to write balanced ternary with the digits -0+");
+	println!("--roman Reads Roman numerals, which must be canonical (MCMXCIV, not MDCCCCLXXXXIIII)");
+	println!("	Numerals above 3999 may use a vinculum (V\u{305} is 5000) or apostrophus (IↃↃ or I)) is 5000)");
+	println!("	Without --roman, numbers are only read as Roman numerals with the prefix '0r', e.g. '-d 0rXLII' gives 42");
+	println!("--roman-lenient Reads Roman numerals without checking they are canonical, e.g. IIII or IC");
+	println!("--toroman <style> Writes numbers as Roman numerals, where style is standard (up to 3999),");
+	println!("	vinculum (up to 3999999) or apostrophus (up to 399999)");
+	println!("--base <encoding> Reads numbers written in a text encoding of their big-endian bytes");
+	println!("	encoding is one of: base58 (Bitcoin), base32, base32hex, crockford (base 32 of the number), base64 or base64url");
+	println!("	Options may follow with ':', e.g. 'base64url:pad': pad or nopad (pad is the default for base32 and base64)");
+	println!("	and zeros, which keeps leading zero bytes up to the width (-w), written as leading 1s in base58");
+	println!("--tobase <encoding> Writes numbers in a text encoding of their big-endian bytes, e.g. '--tobase base64 65537' gives AQAB");
+	println!("--varint <encoding> Reads a variable-length integer from its bytes in hexadecimal, e.g. '-d --varint uleb128 E58E26' gives 624485");
+	println!("	encoding is one of: uleb128, sleb128, protobuf (int64 and uint64 fields), zigzag (sint fields),");
+	println!("	quic (RFC 9000 variable-length integers), vlq (big-endian 7 bits per byte, as in MIDI) or git (pack offsets)");
+	println!("--tovarint <encoding> Writes numbers as the bytes of a variable-length integer, e.g. '-c --tovarint uleb128 624485' gives 0xE5 8E 26");
+	println!("--item <format> Reads an integer item from its bytes in hexadecimal, where format is cbor or msgpack");
+	println!("--toitem <format> Writes numbers as the shortest integer item, e.g. '--toitem cbor 1000' gives 0x1903E8");
+	println!("--itemfields <format> Writes the bytes of the item and what its header means, e.g. '--itemfields cbor 1000' gives");
+	println!("	0x1903E8 header=0x19 major=0 (unsigned integer) additional=25 (2 byte argument) argument=0x03E8 value=1000");
+	println!("--der Reads an ASN.1 DER INTEGER from its tag, length and value bytes in hexadecimal, e.g. '-d --der 0203010001' gives 65537");
+	println!("--toder Writes numbers as a DER INTEGER, the fewest two's complement bytes after the tag and length");
+	println!("	Positive numbers with the high bit set get a leading 0x00, e.g. '-c --toder 128' gives 0x02 02 00 80");
+	println!("--oid Reads a dotted object identifier as its DER bytes, e.g. '--oid 1.2.840.113549' gives 0x06062A864886F70D");
+	println!("--tooid Writes the DER bytes of an object identifier in dotted form, e.g. '--tooid 0x06062A864886F70D'");
+	println!("--protobuf Writes the bytes of a protobuf message as a tree of its fields, without a schema");
+	println!("	Each line has the field number, wire type and value, e.g. '--protobuf 0x089601' gives '1: varint 150 (sint 75)'");
+	println!("	Varints also show their int64 and ZigZag (sint) values, fixed fields their int and float values,");
+	println!("	and length-delimited fields are shown as a nested message if they decode as one, or else as a string or bytes");
+	println!("--codepoint Reads a Unicode code point written as U+1F600, \\u{{1F600}}, \\uD83D\\uDE00 or the character itself, e.g. '-d --codepoint U+E9' gives 233");
+	println!("--utf <encoding> Reads the bytes of one character in hexadecimal, where encoding is utf8, utf16, utf16le, utf32 or utf32le");
+	println!("	e.g. '--tocodepoint --utf utf8 F09F9880' gives U+1F600");
+	println!("--tocodepoint Writes numbers as Unicode code points, e.g. '--tocodepoint 0x1F600' gives U+1F600");
+	println!("--toutf <encoding> Writes the bytes of a code point, in code units for big-endian UTF-16 and UTF-32");
+	println!("	e.g. '-c --codepoint --toutf utf8 U+1F600' gives 0xF0 9F 98 80 and with '--toutf utf16' gives 0xD83D DE00");
+	println!("--utffields <encoding> Writes the bytes of a code point followed by its name and general category, e.g. '--codepoint --utffields utf8 é' gives");
+	println!("	0xC3A9 U+00E9 name=LATIN SMALL LETTER E WITH ACUTE category=Ll");
+	println!("	Names are only known for common blocks (Latin, Greek, Cyrillic, punctuation, symbols, arrows, emoji), CJK ideographs and Hangul,");
+	println!("	other characters such as Hebrew, Arabic, Thai, kana and fullwidth forms are written with name=<not in table>");
+	println!("--text <encoding> Reads input as text in an encoding: ascii, latin1, utf8 or cp037 (EBCDIC)");
+	println!("	Escapes such as \\n, \\x7F, \\177, \\u{{1F600}} and \\U0001F600 may be used; \\x and octal escapes are written as bytes");
+	println!("--totext <encoding>[:<escapes>] Writes bytes as text, with escapes for characters that are not printable");
+	println!("	escapes is c (default), rust or python, e.g. '--totext ascii:python 0x48690AFF' gives Hi\\n\\xFF");
+	println!("--tochar Writes numbers as a character literal of their bytes, e.g. '--tochar 0x6D703461' gives 'mp4a'");
+	println!("--dump [file] Dumps a file, or stdin if no file is given, as lines of offset, hexadecimal bytes and an ASCII gutter like xxd");
+	println!("	e.g. '00000000: 4865 6C6C 6F0A                           Hello.', where groups are separated as set by -c=<sep> or -t");
+	println!("	Hexadecimal is uppercase unless -xl is given. With --dump, -r turns such a dump (or plain hexadecimal) back into bytes");
+	println!("--cols <n> Sets the bytes per line of a dump (default 16)");
+	println!("--group <n> Sets the bytes per group of a dump (default 2), where 0 puts every line in one group");
+	println!("--seek <n> Starts a dump at byte n of the input");
+	println!("--len <n> Stops a dump after n bytes");
+	println!("--fdigits <n> Sets the maximum number of digits written after a radix point (default 64)");
+	println!("	Numbers may have a radix point in any base, e.g. '0x1.8' or '0.1'");
+	println!("	Repeating digits are put in parentheses, e.g. '-b 0.1' gives '0b0.0(0011)', and '...' marks cut off digits");
+	println!();
+	println!("Operations (applied in order to every number that follows them):");
+	println!("--set <bits> Sets the given bits, e.g. '--set 7' or '--set 3,4' or '--set 15:8'");
+	println!("--clear <bits> Clears the given bits");
+	println!("--toggle <bits> Inverts the given bits");
+	println!("--extract <hi:lo> Replaces the number with bits [hi:lo] of itself");
+	println!("--insert <hi:lo>=<num> Writes num into bits [hi:lo]");
+	println!("	Bits are indexed from 0 (least significant bit). Fixed widths (-w) are never exceeded");
+	println!("--bswap Reverses the order of the bytes");
+	println!("--le Reverses the order of the bytes and separates them, giving the little-endian byte sequence");
+	println!("	Read a little-endian dump back with '-X --bswap \"78 56 34 12\"', as whitespace is allowed between bytes with these operations");
+	println!("--wswap16 Swaps the 16-bit halves of every 32-bit word");
+	println!("--wswap32 Swaps the 32-bit halves of every 64-bit word");
+	println!("--pdp Converts to and from PDP-11 middle-endian by swapping the bytes of every 16-bit word");
+	println!("	Byte order operations work on the width set by -w, or on whole bytes/words when unfixed");
+	println!("--rev Reverses the order of the bits");
+	println!("--rotl <n> Rotates the bits left by n");
+	println!("--rotr <n> Rotates the bits right by n");
+	println!("	Reversing and rotating work on the width set by -w, or on whole bytes when unfixed");
+	println!("--nswap Swaps the nibbles of every byte");
+	println!("--pdep <mask> Deposits the low bits into the positions of the set bits of mask (BMI2 PDEP)");
+	println!("--pext <mask> Gathers the bits at the set bits of mask into the low bits (BMI2 PEXT)");
+	println!("--gray-encode Converts to reflected binary Gray code, e.g. 0b1011 gives 0b1110");
+	println!("--gray-decode Converts from reflected binary Gray code");
+	println!("--sext <from>:<to> Sign-extends the low 'from' bits to 'to' bits, e.g. '--sext 12:32'");
+	println!("--zext <from>:<to> Zero-extends the low 'from' bits to 'to' bits");
+	println!("--trunc <to> Truncates to the low 'to' bits");
+	println!("	With a fixed width (-w) the 'to' bits are zero-extended to the width, e.g. '-w=4 --sext 8:16 0x80' gives 0x0000FF80");
+	println!("--reset Removes all operations");
 }
 
 /// Multiplies the value the integer represented by the bitvec by -1 using two's complement
@@ -88,16 +349,40 @@ fn negative(bits: &mut BitVec) {
 	}
 }
 
+/// Decides how to read the (positive) arg based off its prefix or the digits it uses
+fn interpret_mode(positive_arg: &str) -> ReadMode {
+	if positive_arg.starts_with("0x") && positive_arg.contains(['p', 'P']) {
+		ReadMode::Float(float::F64)
+	} else if positive_arg.starts_with('\'') || positive_arg.starts_with("b'") {
+		ReadMode::CharLiteral
+	} else if positive_arg.starts_with("0b") {
+		ReadMode::Binary
+	} else if positive_arg.starts_with("0x") {
+		ReadMode::Hex
+	} else if positive_arg.starts_with("0o") {
+		ReadMode::Octal
+	} else if positive_arg.starts_with("0r") {
+		ReadMode::Roman(false)
+	} else if positive_arg.contains(['a', 'b', 'c', 'd', 'e', 'f', 'A', 'B', 'C', 'D', 'E', 'F']) {
+		ReadMode::Hex
+	} else {
+		ReadMode::Decimal
+	}
+}
+
 /// Attempts to parse the string arg into an integer
 /// The result integer is returned as an integer stored in the bitvec (signedness indicated by signed_mode)
 /// On failure, returns an Err with an error message
+#[allow(clippy::get_first, clippy::is_digit_ascii_radix, clippy::needless_return)]
 fn read(arg: &String, mut read_mode: ReadMode, write_mode: WriteMode, write_length: WriteLength, signed_mode: bool) -> Result<BitVec, String> {
 	let mut negative_arg = false;
 	// strip all prefixes from the arg and interpret
 	let stripped_arg = {
 		// strip negative sign from the arg
 		let positive_arg = {
-			if let Some(tmp_arg) = arg.strip_prefix('-') {
+			// balanced ternary and some text encodings may use '-' as a digit, and code points and text may have the character '-'
+			let is_digit = matches!(read_mode, ReadMode::Radix(Radix::BalancedTernary(_)) | ReadMode::BaseEncoded(_) | ReadMode::CodePoint | ReadMode::Text(_));
+			if let (Some(tmp_arg), false) = (arg.strip_prefix('-'), is_digit) {
 				negative_arg = true;
 				tmp_arg
 			} else {
@@ -105,6 +390,11 @@ fn read(arg: &String, mut read_mode: ReadMode, write_mode: WriteMode, write_leng
 			}
 		};
 
+		// decide how to read the arg if it is up to the program
+		if read_mode == ReadMode::Interpret {
+			read_mode = interpret_mode(positive_arg);
+		}
+
 		// strip prefix depending on read_mode
 		match read_mode {
 			ReadMode::Binary => {
@@ -114,7 +404,7 @@ fn read(arg: &String, mut read_mode: ReadMode, write_mode: WriteMode, write_leng
 					positive_arg
 				}
 			}
-			ReadMode::Hex => {
+			ReadMode::Hex | ReadMode::Bcd(_) | ReadMode::Varint(_) | ReadMode::Item(_) | ReadMode::DerInteger | ReadMode::Utf(_) => {
 				if let Some(tmp_arg) = positive_arg.strip_prefix("0x") {
 					tmp_arg
 				} else {
@@ -128,39 +418,34 @@ fn read(arg: &String, mut read_mode: ReadMode, write_mode: WriteMode, write_leng
 					positive_arg
 				}
 			}
-			ReadMode::Decimal => {
-				positive_arg
-			}
-			ReadMode::Interpret => {
-				if let Some(tmp_arg) = positive_arg.strip_prefix("0b") {
-					read_mode = ReadMode::Binary;
-					tmp_arg
-				} else if let Some(tmp_arg) = positive_arg.strip_prefix("0x") {
-					read_mode = ReadMode::Hex;
-					tmp_arg
-				} else if let Some(tmp_arg) = positive_arg.strip_prefix("0o") {
-					read_mode = ReadMode::Octal;
+			ReadMode::Roman(_) => {
+				if let Some(tmp_arg) = positive_arg.strip_prefix("0r") {
 					tmp_arg
-				} else if positive_arg.contains(['a', 'b', 'c', 'd', 'e', 'f', 'A', 'B', 'C', 'D', 'E', 'F']) {
-					read_mode = ReadMode::Hex;
-					positive_arg
 				} else {
-					read_mode = ReadMode::Decimal;
 					positive_arg
 				}
 			}
+			ReadMode::Decimal | ReadMode::FixedPoint(_) | ReadMode::Float(_) | ReadMode::Dfp(_) | ReadMode::Radix(_) | ReadMode::BaseEncoded(_) | ReadMode::Oid | ReadMode::CodePoint | ReadMode::Text(_) | ReadMode::CharLiteral => {
+				positive_arg
+			}
+			ReadMode::Interpret => panic!()
 		}
 	};
 	
-	// check negative arguments make sense
-	if !signed_mode && negative_arg {
+	// check negative arguments make sense (floats have their own sign bit)
+	let is_float = matches!(read_mode, ReadMode::Float(_) | ReadMode::Dfp(_));
+	if !signed_mode && negative_arg && !is_float {
 		return Err("Negative numbers not allowed in unsigned mode".to_string());
 	}
-	if read_mode != ReadMode::Decimal && negative_arg {
+	if read_mode != ReadMode::Decimal && !matches!(read_mode, ReadMode::FixedPoint(_)) && negative_arg && !is_float {
 		return Err("- operator is only allowed with decimal numbers".to_string());
 	}
 
 	let mut bits = BitVec::new();
+	// numbers read from BCD and the negative-base systems may be negative without a '-'
+	let mut value_negative = false;
+	// encodings that are read as bytes keep their leading zero bits
+	let mut read_width = 0;
 	match read_mode {
 		ReadMode::Binary => {
 			for c in stripped_arg.chars() {
@@ -339,6 +624,7 @@ fn read(arg: &String, mut read_mode: ReadMode, write_mode: WriteMode, write_leng
 			}
 
 			/// returns if the integer that the string would parse into is even
+			#[allow(clippy::match_like_matches_macro, clippy::ptr_arg)]
 			fn string_is_even(str: &Vec<char>) -> bool {
 				match str.last() {
 					Some('0') | Some('2') | Some('4') | Some('6') | Some('8') => true,
@@ -347,6 +633,7 @@ fn read(arg: &String, mut read_mode: ReadMode, write_mode: WriteMode, write_leng
 			}
 
 			/// subtracts one from the integer represented by the string
+			#[allow(clippy::get_first)]
 			fn sub_string(str: &mut Vec<char>) {
 				// subtract off numbers
 				for index in (0..str.len()).rev() {
@@ -375,6 +662,7 @@ fn read(arg: &String, mut read_mode: ReadMode, write_mode: WriteMode, write_leng
 			}
 
 			/// divides the integer represented by the string by 2
+			#[allow(clippy::needless_range_loop)]
 			fn div_string(str: &mut Vec<char>) {
 				// divide numbers starting in the front
 				let mut carry = false;
@@ -503,6 +791,93 @@ fn read(arg: &String, mut read_mode: ReadMode, write_mode: WriteMode, write_leng
 				};
 			}
 		}
+		ReadMode::FixedPoint(q) => {
+			bits = fixed::read_fixed(stripped_arg, negative_arg, q, signed_mode)?;
+		}
+		ReadMode::Float(format) => {
+			bits = float::read_float(stripped_arg, negative_arg, format)?;
+		}
+		ReadMode::Dfp(format) => {
+			bits = dfp::read_dfp(stripped_arg, negative_arg, format)?;
+		}
+		ReadMode::Bcd(kind) => {
+			let (is_negative, digits) = bcd::read_bcd(stripped_arg, kind)?;
+			if is_negative && !signed_mode {
+				return Err("Negative numbers not allowed in unsigned mode".to_string());
+			}
+			value_negative = is_negative;
+			bits = read(&digits, ReadMode::Decimal, WriteMode::Binary, WriteLength::Unfixed, false)?;
+		}
+		ReadMode::Radix(radix) => {
+			let value = radix::read_radix(stripped_arg, radix)?;
+			if value.negative && !signed_mode {
+				return Err("Negative numbers not allowed in unsigned mode".to_string());
+			}
+			value_negative = value.negative;
+			bits = read(&value.int_str(), ReadMode::Decimal, WriteMode::Binary, WriteLength::Unfixed, false)?;
+		}
+		ReadMode::Roman(lenient) => {
+			let value = roman::read_roman(stripped_arg, lenient)?;
+			bits = read(&value.to_string(), ReadMode::Decimal, WriteMode::Binary, WriteLength::Unfixed, false)?;
+		}
+		ReadMode::BaseEncoded(encoding) => {
+			bits = baseenc::decode(stripped_arg, encoding)?;
+		}
+		ReadMode::Varint(varint) => {
+			let (is_negative, magnitude) = varint::decode(stripped_arg, varint)?;
+			if is_negative && !signed_mode {
+				return Err("Negative numbers not allowed in unsigned mode".to_string());
+			}
+			value_negative = is_negative;
+			bits = magnitude;
+		}
+		ReadMode::DerInteger => {
+			let (is_negative, magnitude) = der::decode_integer(stripped_arg)?;
+			if is_negative && !signed_mode {
+				return Err("Negative numbers not allowed in unsigned mode".to_string());
+			}
+			value_negative = is_negative;
+			bits = magnitude;
+		}
+		ReadMode::Oid => {
+			let bytes = der::encode_oid(stripped_arg)?;
+			bits = bytes::bytes_to_bits(&bytes);
+			read_width = bits.len() as u64;
+		}
+		ReadMode::CharLiteral => {
+			let values = text::read_char_literal(stripped_arg)?;
+			bits = match values[..] {
+				[value] => (0..21).rev().map(|i| (value >> i) & 1 == 1).collect(),
+				_ => {
+					// multi-character constants keep their leading zero bytes
+					read_width = values.len() as u64 * 8;
+					values.iter().flat_map(|value| bytes::bits_of(*value as u128, 8)).collect()
+				}
+			};
+		}
+		ReadMode::Item(format) => {
+			let (is_negative, magnitude) = item::decode(stripped_arg, format)?;
+			if is_negative && !signed_mode {
+				return Err("Negative numbers not allowed in unsigned mode".to_string());
+			}
+			value_negative = is_negative;
+			bits = magnitude;
+		}
+		ReadMode::CodePoint | ReadMode::Utf(_) => {
+			let code_point = match read_mode {
+				ReadMode::Utf(utf) => unicode::decode(stripped_arg, utf)?,
+				_ => unicode::read_code_point(stripped_arg)?
+			};
+			bits = (0..21).rev().map(|i| (code_point >> i) & 1 == 1).collect();
+		}
+		ReadMode::Text(encoding) => {
+			let bytes = text::read_text(stripped_arg, encoding)?;
+			if bytes.is_empty() {
+				return Err("Missing text".to_string());
+			}
+			bits = bytes::bytes_to_bits(&bytes);
+			read_width = bits.len() as u64;
+		}
 		ReadMode::Interpret => panic!()
 	};
 
@@ -511,30 +886,61 @@ fn read(arg: &String, mut read_mode: ReadMode, write_mode: WriteMode, write_leng
 		bits.remove(0);
 	};
 
+	// fixed and floating point numbers are always as wide as their format
+	let format_width = match read_mode {
+		ReadMode::FixedPoint(q) => q.width(),
+		ReadMode::Float(format) => format.width(),
+		ReadMode::Dfp(format) => format.width(),
+		_ => read_width
+	};
+	while (bits.len() as u64) < format_width {
+		bits.insert(0, false);
+	}
+
 	// increase length of bits to write_length
-	let target_len = match write_length {
-		WriteLength::Unfixed => {
-			let min_len = bits.len() as u64;
-			let int = match write_mode {
-				WriteMode::Hex(_) => 4u64,
-				WriteMode::Octal => 3u64,
-				WriteMode::Binary | WriteMode::Decimal => 1u64
-			};
-			min_len.next_multiple_of(int)
-		}
-		WriteLength::RoundUp => {
-			let min_len = bits.len() as u64;
-			let int = match write_mode {
-				WriteMode::Binary | WriteMode::Hex(_) => 8u64,
-				WriteMode::Octal => 6u64,
-				WriteMode::Decimal => 1u64
-			};
-			min_len.next_multiple_of(int)
-		}
-		WriteLength::Fixed(len) => match write_mode {
-			WriteMode::Decimal => bits.len() as u64,
-			WriteMode::Octal => len * 6,
-			WriteMode::Binary | WriteMode::Hex(_) => len * 8
+	fit_length(&mut bits, write_mode, write_length)?;
+
+	// flip bits and add one if reading from decimal and negative
+	if (negative_arg && (read_mode == ReadMode::Decimal || matches!(read_mode, ReadMode::FixedPoint(_)))) || value_negative {
+		negative(&mut bits);
+	}
+	
+	return Ok(bits);
+}
+
+/// Pads the bits with leading zeroes up to the length given by write_length
+/// Returns an Err if the bits are too long to fit in a fixed length
+fn fit_length(bits: &mut BitVec, write_mode: WriteMode, write_length: WriteLength) -> Result<(), String> {
+	// fixed-point numbers are never narrower than their format
+	let min_len = match write_mode {
+		WriteMode::FixedPoint(q) => (bits.len() as u64).max(q.width()),
+		_ => bits.len() as u64
+	};
+	let target_len = if let Some(width) = format_width(write_mode) {
+		// float bit patterns are always exactly as wide as their format
+		width
+	} else {
+		match write_length {
+			WriteLength::Unfixed => {
+				let int = match write_mode {
+					WriteMode::Hex(_) => 4u64,
+					WriteMode::Octal => 3u64,
+					_ if write_mode.is_byte_sequence() => 8u64,
+					_ => 1u64
+				};
+				min_len.next_multiple_of(int)
+			}
+			WriteLength::RoundUp => {
+				let int = match write_mode {
+					WriteMode::Decimal => 1u64,
+					_ => write_mode.width_unit()
+				};
+				min_len.next_multiple_of(int)
+			}
+			WriteLength::Fixed(len) => match write_mode {
+				WriteMode::Decimal => bits.len() as u64,
+				_ => len * write_mode.width_unit()
+			}
 		}
 	};
 	if (bits.len() as u64) > target_len {
@@ -543,24 +949,88 @@ fn read(arg: &String, mut read_mode: ReadMode, write_mode: WriteMode, write_leng
 	while (bits.len() as u64) < target_len {
 		bits.insert(0, false);
 	}
+	Ok(())
+}
 
-	// flip bits and add one if reading from decimal and negative
-	if negative_arg && read_mode == ReadMode::Decimal {
-		negative(&mut bits);
+/// Gets the width of the float format written by write_mode, or None if it does not write floats
+fn format_width(write_mode: WriteMode) -> Option<u64> {
+	match write_mode {
+		WriteMode::HexFloat(format) | WriteMode::FloatFields(format) => Some(format.width()),
+		WriteMode::Dfp(format) | WriteMode::DfpFields(format) => Some(format.width()),
+		_ => None
+	}
+}
+
+/// Gets the fixed width of the output in bits, or None if the output width is not fixed
+fn fixed_width(write_mode: WriteMode, write_length: WriteLength) -> Option<usize> {
+	if let Some(width) = format_width(write_mode) {
+		return Some(width as usize);
+	}
+	match write_length {
+		WriteLength::Fixed(len) => Some((len * write_mode.width_unit()) as usize),
+		WriteLength::Unfixed | WriteLength::RoundUp => None
+	}
+}
+
+/// Gets the bytes of a message that never starts with a zero byte (a DER tag or a protobuf key) from the bits,
+/// without the zero bytes a fixed width adds in front
+fn message_bytes(bits: &BitVec) -> Vec<u8> {
+	let bytes = bytes::bits_to_bytes(bits);
+	bytes.into_iter().skip_while(|b| *b == 0).collect()
+}
+
+/// Gets the code point the bits hold, or an Err if they are not between 0 and U+10FFFF
+fn code_point(bits: &BitVec, signed_mode: bool) -> Result<u32, String> {
+	match varint::to_i128(bits, signed_mode) {
+		Some(value) if (0..=unicode::MAX_CODE_POINT as i128).contains(&value) => Ok(value as u32),
+		_ => Err("Code points are between 0 and U+10FFFF".to_string())
 	}
-	
-	return Ok(bits);
 }
 
 /// Converts the stream of bits representing a little-endian integer (signedness indicated by signed_mode) into
 /// a string version of the integer in the format given by write_mode
+#[allow(clippy::needless_borrow, clippy::needless_range_loop, clippy::needless_return)]
 fn write(mut bits: &mut BitVec, write_mode: WriteMode, write_separator: &WriteSeparator, signed_mode: bool, write_prefix: bool) -> String {
+	if let WriteMode::Stats = write_mode {
+		return stats::report(bits);
+	}
+	if let WriteMode::FixedPoint(q) = write_mode {
+		return fixed::write_fixed(bits, q, signed_mode);
+	}
+	if let WriteMode::HexFloat(format) = write_mode {
+		return float::write_hex_float(bits, format);
+	}
+	if let WriteMode::FloatFields(format) = write_mode {
+		return float::write_fields(bits, format);
+	}
+	if let WriteMode::Dfp(format) = write_mode {
+		return dfp::write_dfp(bits, format);
+	}
+	if let WriteMode::DfpFields(format) = write_mode {
+		return dfp::write_fields(bits, format);
+	}
+	if let WriteMode::DerInteger = write_mode {
+		let mut der_bits = bytes::bytes_to_bits(&der::encode_integer(bits, signed_mode));
+		return write(&mut der_bits, WriteMode::Hex(true), write_separator, false, write_prefix);
+	}
+	if let WriteMode::Text(format) = write_mode {
+		let bytes = bytes::bits_to_bytes(bits);
+		return text::write_text(&bytes, format);
+	}
+	if let WriteMode::CharLiteral = write_mode {
+		let bytes = bytes::bits_to_bytes(bits);
+		return text::write_char_literal(if bytes.is_empty() { &[0] } else { &bytes });
+	}
+	if let WriteMode::BaseEncoded(encoding) = write_mode {
+		return baseenc::encode(bits, encoding);
+	}
+
 	let mut ret_str = if write_prefix {
 		match write_mode {
 			WriteMode::Binary => "0b",
 			WriteMode::Octal => "0o",
 			WriteMode::Hex(_) => "0x",
-			WriteMode::Decimal => ""
+			_ => ""
 		}.to_string()
 	} else {
 		String::new()
@@ -739,13 +1209,13 @@ fn write(mut bits: &mut BitVec, write_mode: WriteMode, write_separator: &WriteSe
 			};
 			4
 		}
-		WriteMode::Decimal => panic!(),
+		_ => panic!(),
 	};
 
 	let ideal_chars_in_group = match write_mode {
 		WriteMode::Binary => 8,
 		WriteMode::Octal | WriteMode::Hex(_) => 2,
-		WriteMode::Decimal => panic!()
+		_ => panic!()
 	};
 
 	// number of chars already added to the group for emplacing separators
@@ -777,23 +1247,213 @@ fn write(mut bits: &mut BitVec, write_mode: WriteMode, write_separator: &WriteSe
 	return ret_str;
 }
 
+/// Writes the bits like write, but also in the write modes that cannot hold every number, returning an Err for the numbers they cannot
+/// Encodings that are longer than the number itself are fitted to write_length after encoding
+fn try_write(bits: &mut BitVec, write_mode: WriteMode, write_length: WriteLength, write_separator: &WriteSeparator, signed_mode: bool, write_prefix: bool) -> Result<String, String> {
+	if !write_mode.is_fallible() {
+		return Ok(write(bits, write_mode, write_separator, signed_mode, write_prefix));
+	}
+	match write_mode {
+		WriteMode::Bcd(kind) => {
+			// encode the decimal digits and write them as hexadecimal
+			let digits = write(bits, WriteMode::Decimal, &WriteSeparator::None, signed_mode, false);
+			let (is_negative, digits) = match digits.strip_prefix('-') {
+				Some(digits) => (true, digits),
+				None => (false, digits.as_str())
+			};
+			let mut bcd_bits = bcd::write_bcd(digits, is_negative, kind, signed_mode)?;
+			fit_length(&mut bcd_bits, write_mode, write_length)?;
+			Ok(write(&mut bcd_bits, WriteMode::Hex(true), write_separator, false, write_prefix))
+		}
+		WriteMode::Radix(radix) => {
+			// zero padded to a fixed width is written without any digits
+			let digits = write(bits, WriteMode::Decimal, &WriteSeparator::None, signed_mode, false);
+			let value = decimal::Decimal::parse(if digits.is_empty() { "0" } else { &digits })?;
+			radix::check_range(&value, radix)?;
+			Ok(radix::write_radix(&value, radix))
+		}
+		WriteMode::Roman(style) => {
+			// zero padded to a fixed width is written without any digits
+			let value = write(bits, WriteMode::Decimal, &WriteSeparator::None, signed_mode, false);
+			let value = if value.is_empty() { "0".to_string() } else { value };
+			roman::check_range(&value, style)?;
+			Ok(roman::write_roman(value.parse().unwrap(), style))
+		}
+		WriteMode::Varint(varint) => {
+			let mut varint_bits = bytes::bytes_to_bits(&varint::encode(bits, signed_mode, varint)?);
+			Ok(write(&mut varint_bits, WriteMode::Hex(true), write_separator, false, write_prefix))
+		}
+		WriteMode::Item(format) | WriteMode::ItemFields(format) => {
+			let bytes = item::encode(bits, signed_mode, format)?;
+			let mut item_bits = bytes::bytes_to_bits(&bytes);
+			let hex = write(&mut item_bits, WriteMode::Hex(true), write_separator, false, write_prefix);
+			Ok(match write_mode {
+				WriteMode::ItemFields(_) => format!("{} {}", hex, item::write_fields(&bytes, format)),
+				_ => hex
+			})
+		}
+		WriteMode::Protobuf => protobuf::write_message(&message_bytes(bits)),
+		WriteMode::Oid => der::decode_oid(&message_bytes(bits)),
+		WriteMode::CodePoint => Ok(unicode::write_code_point(code_point(bits, signed_mode)?)),
+		WriteMode::Utf(utf) | WriteMode::UtfFields(utf) => {
+			// write each code unit of big-endian encodings as one group, and the bytes of little-endian encodings
+			let code_point = code_point(bits, signed_mode)?;
+			let units: Vec<String> = unicode::encode(code_point, utf)?.chunks(utf.group_len()).map(|unit| {
+				let mut unit_bits = bytes::bytes_to_bits(unit);
+				write(&mut unit_bits, WriteMode::Hex(true), &WriteSeparator::None, false, false)
+			}).collect();
+			let hex = match write_separator {
+				WriteSeparator::Separator(sep) => units.join(sep),
+				WriteSeparator::RuntimeDetermine | WriteSeparator::None => units.concat()
+			};
+			let prefix = if write_prefix { "0x" } else { "" };
+			Ok(match write_mode {
+				WriteMode::UtfFields(_) => format!("{}{} {}", prefix, hex, unicode::write_fields(code_point)),
+				_ => format!("{}{}", prefix, hex)
+			})
+		}
+		_ => panic!()
+	}
+}
+
 /// Converts the given argument into the specified format and returns either the converted string or an error message
-fn convert(ref arg: &String, read_mode: ReadMode, write_mode: WriteMode, write_length: WriteLength, write_separator: &mut WriteSeparator, signed_mode: bool, write_prefix: bool) -> Result<String, String> {
+fn convert(arg: &String, settings: &mut Settings) -> Result<String, String> {
+	let Settings { read_mode, write_mode, write_length, ref mut write_separator, signed_mode, write_prefix, ref transforms, .. } = *settings;
 	// runtime fix write_separator
 	if let WriteSeparator::RuntimeDetermine = write_separator {
-		*write_separator = WriteSeparator::Separator(match write_mode {
-			WriteMode::Decimal => ',',
-			WriteMode::Binary | WriteMode::Octal | WriteMode::Hex(_) => ' '
-		}.to_string());
+		*write_separator = WriteSeparator::Separator(write_mode.default_separator().to_string());
 	}
 	
+	// numbers with a radix point are converted separately, unless the read mode has its own meaning for it
+	let resolved_mode = match read_mode {
+		ReadMode::Interpret => interpret_mode(arg.strip_prefix('-').unwrap_or(arg)),
+		_ => read_mode
+	};
+	let positional = matches!(resolved_mode, ReadMode::Binary | ReadMode::Octal | ReadMode::Decimal | ReadMode::Hex);
+	if positional && arg.contains('.') {
+		if !transforms.is_empty() {
+			return Err("Operations cannot be applied to fractions".to_string());
+		}
+		return convert_fraction(arg, settings);
+	}
+
+	// byte sequences (such as "78 56 34 12") may have whitespace between the bytes, unless it is part of the text
+	let byte_sequence = transforms.iter().any(Transform::is_byte_order) || write_mode == WriteMode::Protobuf;
+	let joined_arg: String;
+	let arg = if byte_sequence && !matches!(read_mode, ReadMode::CodePoint | ReadMode::Text(_) | ReadMode::CharLiteral) {
+		joined_arg = arg.split_whitespace().collect();
+		&joined_arg
+	} else {
+		arg
+	};
+
 	// do conversion
-	match read(arg, read_mode, write_mode, write_length, signed_mode) {
-		Ok(mut bits) => {
-			Ok(write(&mut bits, write_mode, &write_separator, signed_mode, write_prefix))
+	let mut bits = read(arg, read_mode, write_mode, write_length, signed_mode)?;
+
+	// measure how far the fixed-point value is from the real number before any transforms
+	let quantisation_error = match read_mode {
+		ReadMode::FixedPoint(q) if q.report_error => Some(fixed::quantisation_error(arg, &bits, q, signed_mode)?),
+		_ => None
+	};
+
+	if !transforms.is_empty() {
+		for t in transforms {
+			transform::apply(&mut bits, t, fixed_width(write_mode, write_length), signed_mode)?;
+		}
+
+		// transforms may have cleared high bits, so trim and pad again like read does
+		// byte order operations keep their leading zeroes so the byte sequence stays intact, and casts keep the width they cast to
+		let keeps_width = byte_sequence || transforms.iter().any(Transform::is_cast);
+		if !signed_mode && fixed_width(write_mode, write_length).is_none() && !keeps_width {
+			while !bits.is_empty() && !bits[0] {
+				bits.remove(0);
+			};
+		}
+		fit_length(&mut bits, write_mode, write_length)?;
+	}
+
+	let mut ret_str = try_write(&mut bits, write_mode, write_length, write_separator, signed_mode, write_prefix)?;
+	if let Some(error) = quantisation_error {
+		ret_str.push_str(&format!(" error={}", error));
+	}
+	Ok(ret_str)
+}
+
+/// Converts a number with a radix point, such as "0x1.8" or "-0.1"
+/// The integer part is converted like any other number, and the fractional part is converted exactly up to max_frac_digits
+/// Negative fractions are written with a '-' in every base
+fn convert_fraction(arg: &str, settings: &Settings) -> Result<String, String> {
+	let Settings { read_mode, write_mode, write_length, ref write_separator, signed_mode, write_prefix, max_frac_digits, .. } = *settings;
+	let (positive_arg, negative_arg) = match arg.strip_prefix('-') {
+		Some(tmp_arg) => (tmp_arg, true),
+		None => (arg, false)
+	};
+	let read_mode = match read_mode {
+		ReadMode::Interpret => interpret_mode(positive_arg),
+		_ => read_mode
+	};
+	if !signed_mode && negative_arg {
+		return Err("Negative numbers not allowed in unsigned mode".to_string());
+	}
+	if read_mode != ReadMode::Decimal && negative_arg {
+		return Err("- operator is only allowed with decimal numbers".to_string());
+	}
+
+	let from_base = match read_mode {
+		ReadMode::Binary => 2,
+		ReadMode::Octal => 8,
+		ReadMode::Decimal => 10,
+		ReadMode::Hex => 16,
+		ReadMode::Interpret | ReadMode::FixedPoint(_) | ReadMode::Float(_) | ReadMode::Dfp(_) | ReadMode::Bcd(_) | ReadMode::Radix(_) | ReadMode::Roman(_) | ReadMode::BaseEncoded(_) | ReadMode::Varint(_) | ReadMode::Item(_) | ReadMode::DerInteger | ReadMode::Oid | ReadMode::CodePoint | ReadMode::Utf(_) | ReadMode::Text(_) | ReadMode::CharLiteral => panic!()
+	};
+	let to_base = write_mode.base().ok_or("Fractions can only be written in binary, octal, decimal or hexadecimal")?;
+	let upper = write_mode != WriteMode::Hex(false);
+
+	let (int_part, frac_part) = positive_arg.split_once('.').unwrap();
+	let int_digits = ["0b", "0o", "0x"].iter().find_map(|prefix| int_part.strip_prefix(prefix)).unwrap_or(int_part);
+	if int_digits.is_empty() && frac_part.is_empty() {
+		return Err(format!("{} has no digits", arg));
+	}
+	let mut int_bits = read(&int_part.to_string(), read_mode, write_mode, write_length, false)?;
+	let frac_digits = fraction::convert_fraction(frac_part, from_base, to_base, upper, max_frac_digits)?;
+
+	let mut ret_str = if negative_arg { "-".to_string() } else { String::new() };
+	ret_str.push_str(&write(&mut int_bits, write_mode, write_separator, false, write_prefix));
+	match frac_digits.as_str() {
+		"" => { }
+		// no digits fit, so there is only the mark that some were left out
+		"..." => ret_str.push_str("..."),
+		_ => {
+			ret_str.push('.');
+			ret_str.push_str(&frac_digits);
 		}
+	}
+	Ok(ret_str)
+}
+
+/// Gets the parameter following a long option from the args, exiting if there is none
+fn option_param(args: &mut CircularBuffer<String>, option: &String) -> String {
+	match args.remove() {
+		Ok(param) => param,
+		Err(_) => {
+			println!("Error! Missing parameter for option: {}", option);
+			exit(1);
+		}
+	}
+}
+
+/// Checks if the arg is a balanced ternary number being read, which may start with the digit '-' twice like --+
+fn is_ternary_number(arg: &str, read_mode: ReadMode) -> bool {
+	matches!(read_mode, ReadMode::Radix(Radix::BalancedTernary(_))) && arg.chars().all(|c| "-0+1Tt".contains(c))
+}
+
+/// Unwraps the result of parsing an option parameter, exiting on failure
+fn unwrap_param<T>(result: Result<T, String>) -> T {
+	match result {
+		Ok(val) => val,
 		Err(msg) => {
-			Err(msg)
+			println!("Error! {}", msg);
+			exit(1);
 		}
 	}
 }
@@ -807,12 +1467,12 @@ fn main() {
 	}
 
 	// set standard settings
-	let mut read_mode = ReadMode::Interpret;
-	let mut write_mode = WriteMode::Hex(true);
-	let mut write_length = WriteLength::Unfixed;
-	let mut write_separator = WriteSeparator::None;
-	let mut signed_mode = false;
-	let mut write_prefix = true;
+	let mut settings = Settings::default();
+	let mut q_rounding = Rounding::Nearest;
+	let mut q_report_error = false;
+	let mut dump_mode = false;
+	let mut dump_format = DumpFormat::default();
+	let mut dump_path = None;
 
 	// save space for the results of conversions to be stored in
 	let mut results = CircularBuffer::new(std::env::args().len() - 1);
@@ -821,40 +1481,47 @@ fn main() {
 	while let Ok(arg) = args.remove() {
 		match arg.chars().collect::<Vec<char>>()[..] {
 			['-', 'd'] => {
-				write_mode = WriteMode::Decimal;
+				settings.write_mode = WriteMode::Decimal;
 			}
 			['-', 'b'] => {
-				write_mode = WriteMode::Binary;
+				settings.write_mode = WriteMode::Binary;
 			}
 			['-', 'o'] => {
-				write_mode = WriteMode::Octal;
+				settings.write_mode = WriteMode::Octal;
 			}
 			['-', 'x'] | ['-', 'x', 'u'] => {
-				write_mode = WriteMode::Hex(true);
+				settings.write_mode = WriteMode::Hex(true);
 			}
 			['-', 'x', 'l'] => {
-				write_mode = WriteMode::Hex(false);
+				settings.write_mode = WriteMode::Hex(false);
 			}
 			['-', 'D'] => {
-				read_mode = ReadMode::Decimal;
+				settings.read_mode = ReadMode::Decimal;
 			}
 			['-', 'B'] => {
-				read_mode = ReadMode::Binary;
+				settings.read_mode = ReadMode::Binary;
 			}
 			['-', 'O'] => {
-				read_mode = ReadMode::Octal;
+				settings.read_mode = ReadMode::Octal;
 			}
 			['-', 'X'] => {
-				read_mode = ReadMode::Hex;
+				settings.read_mode = ReadMode::Hex;
 			}
 			['-', 'F'] => {
-				read_mode = ReadMode::Interpret;
+				settings.read_mode = ReadMode::Interpret;
+			}
+			['-', 'S'] => {
+				// text is written as its bytes, with separators between them
+				settings.read_mode = ReadMode::Text(Encoding::Utf8);
+				if let WriteSeparator::None = settings.write_separator {
+					settings.write_separator = WriteSeparator::RuntimeDetermine;
+				}
 			}		
 			['-', 'u'] => {
-				signed_mode = false;
+				settings.signed_mode = false;
 			}
 			['-', 's'] => {
-				signed_mode = true;
+				settings.signed_mode = true;
 			}
 			['-', 'h'] | ['-', 'H'] | ['-', '?'] => {
 				print_help();
@@ -864,17 +1531,19 @@ fn main() {
 				// width
 				let (_, num) = arg.split_at(3);
 				if let Ok(intnum) = num.parse::<u64>() {
-					write_length = WriteLength::Fixed(intnum);
+					settings.write_length = WriteLength::Fixed(intnum);
 				} else {
 					println!("Error! Unrecognizable option: {}", arg);
 					exit(1);
 				}
 			}
 			['-', 'f'] => {
-				write_length = WriteLength::Unfixed;
+				settings.write_length = WriteLength::Unfixed;
 			}
 			['-', 'r'] => {
-				write_length = WriteLength::RoundUp;
+				settings.write_length = WriteLength::RoundUp;
+				// widths mean nothing to dumps, so with --dump (before or after) this reverses the dump like 'xxd -r'
+				dump_format.reverse = true;
 			}
 			['-', 'c', '=', ..] => {
 				// separator
@@ -883,29 +1552,254 @@ fn main() {
 					println!("Error! Empty separator!");
 					exit(1);
 				}
-				write_separator = WriteSeparator::Separator(sep.to_string());
+				settings.write_separator = WriteSeparator::Separator(sep.to_string());
 			}
 			['-', 'c'] => {
-				write_separator = WriteSeparator::RuntimeDetermine;
+				settings.write_separator = WriteSeparator::RuntimeDetermine;
 			}
 			['-', 't'] => {
-				write_separator = WriteSeparator::None;
+				settings.write_separator = WriteSeparator::None;
 			}
 			
 			['-', 'p'] => {
-				write_prefix = true;
+				settings.write_prefix = true;
 			}
 			
 			['-', 'n'] => {
-				write_prefix = false;
+				settings.write_prefix = false;
 			}
 			['-', 'v'] | ['-', 'V'] => {
 				println!("Hex v{}", env!("CARGO_PKG_VERSION"));
 				exit(0);
 			}
+			['-', '-', ..] if !is_ternary_number(&arg, settings.read_mode) => {
+				// long options
+				match arg.as_str() {
+					"--set" => {
+						settings.transforms.push(Transform::Set(unwrap_param(BitRange::parse_list(&option_param(&mut args, &arg)))));
+					}
+					"--clear" => {
+						settings.transforms.push(Transform::Clear(unwrap_param(BitRange::parse_list(&option_param(&mut args, &arg)))));
+					}
+					"--toggle" => {
+						settings.transforms.push(Transform::Toggle(unwrap_param(BitRange::parse_list(&option_param(&mut args, &arg)))));
+					}
+					"--extract" => {
+						settings.transforms.push(Transform::Extract(unwrap_param(BitRange::parse(&option_param(&mut args, &arg)))));
+					}
+					"--insert" => {
+						settings.transforms.push(unwrap_param(Transform::parse_insert(&option_param(&mut args, &arg))));
+					}
+					"--bswap" => {
+						settings.transforms.push(Transform::ByteSwap);
+					}
+					"--le" => {
+						// little-endian byte sequence, written with separators between the bytes
+						settings.transforms.push(Transform::ByteSwap);
+						if let WriteSeparator::None = settings.write_separator {
+							settings.write_separator = WriteSeparator::RuntimeDetermine;
+						}
+					}
+					"--wswap16" => {
+						settings.transforms.push(Transform::WordSwap(16));
+					}
+					"--wswap32" => {
+						settings.transforms.push(Transform::WordSwap(32));
+					}
+					"--pdp" => {
+						settings.transforms.push(Transform::Pdp);
+					}
+					"--rev" => {
+						settings.transforms.push(Transform::Reverse);
+					}
+					"--rotl" | "--rotr" => {
+						settings.transforms.push(unwrap_param(Transform::parse_rotate(&option_param(&mut args, &arg), arg == "--rotl")));
+					}
+					"--nswap" => {
+						settings.transforms.push(Transform::NibbleSwap);
+					}
+					"--gray-encode" => {
+						settings.transforms.push(Transform::GrayEncode);
+					}
+					"--gray-decode" => {
+						settings.transforms.push(Transform::GrayDecode);
+					}
+					"--pdep" | "--pext" => {
+						settings.transforms.push(unwrap_param(Transform::parse_mask(&option_param(&mut args, &arg), arg == "--pdep")));
+					}
+					"--sext" | "--zext" => {
+						settings.transforms.push(unwrap_param(Transform::parse_extend(&option_param(&mut args, &arg), arg == "--sext")));
+					}
+					"--trunc" => {
+						settings.transforms.push(unwrap_param(Transform::parse_truncate(&option_param(&mut args, &arg))));
+					}
+					"--q" => {
+						settings.read_mode = ReadMode::FixedPoint(unwrap_param(QFormat::parse(&option_param(&mut args, &arg), q_rounding, q_report_error)));
+					}
+					"--qreal" => {
+						settings.write_mode = WriteMode::FixedPoint(unwrap_param(QFormat::parse(&option_param(&mut args, &arg), q_rounding, q_report_error)));
+					}
+					"--qround" => {
+						q_rounding = unwrap_param(Rounding::parse(&option_param(&mut args, &arg)));
+						if let ReadMode::FixedPoint(q) = &mut settings.read_mode {
+							q.rounding = q_rounding;
+						}
+					}
+					"--qerr" => {
+						q_report_error = true;
+						if let ReadMode::FixedPoint(q) = &mut settings.read_mode {
+							q.report_error = true;
+						}
+					}
+					"--fdigits" => {
+						let param = option_param(&mut args, &arg);
+						settings.max_frac_digits = unwrap_param(param.parse::<usize>().map_err(|_| format!("Invalid number of digits: {}", param)));
+					}
+					"--float" => {
+						settings.read_mode = ReadMode::Float(unwrap_param(FloatFormat::parse(&option_param(&mut args, &arg))));
+					}
+					"--hexfloat" => {
+						settings.write_mode = WriteMode::HexFloat(unwrap_param(FloatFormat::parse(&option_param(&mut args, &arg))));
+					}
+					"--fields" => {
+						settings.write_mode = WriteMode::FloatFields(unwrap_param(FloatFormat::parse(&option_param(&mut args, &arg))));
+					}
+					"--dfp" => {
+						settings.read_mode = ReadMode::Dfp(unwrap_param(DecFormat::parse(&option_param(&mut args, &arg))));
+					}
+					"--dfpreal" => {
+						settings.write_mode = WriteMode::Dfp(unwrap_param(DecFormat::parse(&option_param(&mut args, &arg))));
+					}
+					"--dfpfields" => {
+						settings.write_mode = WriteMode::DfpFields(unwrap_param(DecFormat::parse(&option_param(&mut args, &arg))));
+					}
+					"--bcd" => {
+						settings.read_mode = ReadMode::Bcd(unwrap_param(BcdKind::parse(&option_param(&mut args, &arg))));
+					}
+					"--tobcd" => {
+						settings.write_mode = WriteMode::Bcd(unwrap_param(BcdKind::parse(&option_param(&mut args, &arg))));
+					}
+					"--radix" => {
+						settings.read_mode = ReadMode::Radix(unwrap_param(Radix::parse(&option_param(&mut args, &arg))));
+					}
+					"--toradix" => {
+						settings.write_mode = WriteMode::Radix(unwrap_param(Radix::parse(&option_param(&mut args, &arg))));
+					}
+					"--roman" => {
+						settings.read_mode = ReadMode::Roman(false);
+					}
+					"--roman-lenient" => {
+						settings.read_mode = ReadMode::Roman(true);
+					}
+					"--toroman" => {
+						settings.write_mode = WriteMode::Roman(unwrap_param(RomanStyle::parse(&option_param(&mut args, &arg))));
+					}
+					"--base" => {
+						settings.read_mode = ReadMode::BaseEncoded(unwrap_param(BaseEncoding::parse(&option_param(&mut args, &arg))));
+					}
+					"--tobase" => {
+						settings.write_mode = WriteMode::BaseEncoded(unwrap_param(BaseEncoding::parse(&option_param(&mut args, &arg))));
+					}
+					"--varint" => {
+						settings.read_mode = ReadMode::Varint(unwrap_param(Varint::parse(&option_param(&mut args, &arg))));
+					}
+					"--tovarint" => {
+						settings.write_mode = WriteMode::Varint(unwrap_param(Varint::parse(&option_param(&mut args, &arg))));
+					}
+					"--item" => {
+						settings.read_mode = ReadMode::Item(unwrap_param(ItemFormat::parse(&option_param(&mut args, &arg))));
+					}
+					"--toitem" => {
+						settings.write_mode = WriteMode::Item(unwrap_param(ItemFormat::parse(&option_param(&mut args, &arg))));
+					}
+					"--itemfields" => {
+						settings.write_mode = WriteMode::ItemFields(unwrap_param(ItemFormat::parse(&option_param(&mut args, &arg))));
+					}
+					"--der" => {
+						settings.read_mode = ReadMode::DerInteger;
+					}
+					"--toder" => {
+						settings.write_mode = WriteMode::DerInteger;
+					}
+					"--oid" => {
+						settings.read_mode = ReadMode::Oid;
+					}
+					"--tooid" => {
+						settings.write_mode = WriteMode::Oid;
+					}
+					"--protobuf" => {
+						settings.write_mode = WriteMode::Protobuf;
+					}
+					"--codepoint" => {
+						settings.read_mode = ReadMode::CodePoint;
+					}
+					"--utf" => {
+						settings.read_mode = ReadMode::Utf(unwrap_param(Utf::parse(&option_param(&mut args, &arg))));
+					}
+					"--tocodepoint" => {
+						settings.write_mode = WriteMode::CodePoint;
+					}
+					"--toutf" => {
+						settings.write_mode = WriteMode::Utf(unwrap_param(Utf::parse(&option_param(&mut args, &arg))));
+					}
+					"--text" => {
+						settings.read_mode = ReadMode::Text(unwrap_param(Encoding::parse(&option_param(&mut args, &arg))));
+					}
+					"--totext" => {
+						settings.write_mode = WriteMode::Text(unwrap_param(TextFormat::parse(&option_param(&mut args, &arg))));
+					}
+					"--tochar" => {
+						settings.write_mode = WriteMode::CharLiteral;
+					}
+					"--utffields" => {
+						settings.write_mode = WriteMode::UtfFields(unwrap_param(Utf::parse(&option_param(&mut args, &arg))));
+					}
+					"--dump" => {
+						// groups of bytes are written with separators between them
+						dump_mode = true;
+						if let WriteSeparator::None = settings.write_separator {
+							settings.write_separator = WriteSeparator::RuntimeDetermine;
+						}
+					}
+					"--cols" => {
+						dump_format.cols = unwrap_param(dump::parse_count(&option_param(&mut args, &arg)));
+						if dump_format.cols == 0 {
+							println!("Error! A dump needs at least one byte per line");
+							exit(1);
+						}
+					}
+					"--group" => {
+						dump_format.group = unwrap_param(dump::parse_count(&option_param(&mut args, &arg)));
+					}
+					"--seek" => {
+						dump_format.seek = unwrap_param(dump::parse_count(&option_param(&mut args, &arg)));
+					}
+					"--len" => {
+						dump_format.len = Some(unwrap_param(dump::parse_count(&option_param(&mut args, &arg))));
+					}
+					"--stats" => {
+						settings.write_mode = WriteMode::Stats;
+					}
+					"--reset" => {
+						settings.transforms.clear();
+					}
+					_ => {
+						println!("Error! Unrecognizable option: {}", arg);
+						exit(1);
+					}
+				}
+			}
+			_ if dump_mode => {
+				// the file to dump
+				if dump_path.is_some() {
+					println!("Error! Only one file can be dumped at a time");
+					exit(1);
+				}
+				dump_path = Some(arg);
+			}
 			_ => {
 				// something else (assume number)
-				match convert(&arg, read_mode, write_mode, write_length, &mut write_separator, signed_mode, write_prefix) {
+				match convert(&arg, &mut settings) {
 					Ok(str) => {
 						let _ = results.add(str);
 					}
@@ -918,6 +1812,18 @@ fn main() {
 		};
 	}
 
+	// dump the file or stdin after any results calculated so far
+	if dump_mode {
+		while let Ok(res) = results.remove() {
+			println!("{}", res);
+		}
+		if let Err(str) = dump::run(dump_path.as_deref(), &dump_format, settings.write_mode != WriteMode::Hex(false), &settings.write_separator) {
+			println!("Error! {}", str);
+			exit(1);
+		}
+		exit(0);
+	}
+
 	// print any results calculated so far
 	if results.size() > 0 {
 		while let Ok(res) = results.remove() {
@@ -937,7 +1843,7 @@ fn main() {
 			Ok(_) => {
 				// presumed number
 				line = line.trim().to_string();
-				match convert(&line, read_mode, write_mode, write_length, &mut write_separator, signed_mode, write_prefix) {
+				match convert(&line, &mut settings) {
 					Ok(str) => {
 						println!("{}", str);
 					}
@@ -961,6 +1867,14 @@ mod tests {
 
     use crate::*;
 
+	/// Converts with the default settings apart from the modes given
+	fn convert(arg: &String, read_mode: ReadMode, write_mode: WriteMode, write_length: WriteLength, write_separator: &mut WriteSeparator, signed_mode: bool, write_prefix: bool) -> Result<String, String> {
+		let mut settings = Settings { read_mode, write_mode, write_length, write_separator: std::mem::replace(write_separator, WriteSeparator::None), signed_mode, write_prefix, ..Settings::default() };
+		let ret = crate::convert(arg, &mut settings);
+		*write_separator = settings.write_separator;
+		ret
+	}
+
 	fn bitvec_of_num(mut num: u64) -> BitVec {
 		let mut bv: BitVec = BitVec::new();
 		while num != 0 {
@@ -971,6 +1885,7 @@ mod tests {
 		bv
 	}
 
+	#[allow(clippy::manual_is_multiple_of)]
 	fn padded_bitvec_of_num(num: u64, pad: usize) -> BitVec {
 		let mut bv = bitvec_of_num(num);
 		while bv.len() % pad != 0 {
@@ -994,6 +1909,18 @@ mod tests {
 		assert_eq!(bv, bitvec_of_num(139))
 	}
 
+	#[test]
+	fn write_mode_tests() {
+		assert!(WriteMode::Protobuf.is_byte_sequence() && WriteMode::Protobuf.is_fallible());
+		assert!(!WriteMode::Hex(true).is_byte_sequence() && !WriteMode::Hex(true).is_fallible());
+		assert_eq!(WriteMode::Hex(true).default_separator(), ' ');
+		assert_eq!(WriteMode::Decimal.default_separator(), ',');
+		assert_eq!(WriteMode::Octal.base(), Some(8));
+		assert_eq!(WriteMode::Stats.base(), None);
+		assert_eq!(WriteMode::Octal.width_unit(), 6);
+		assert_eq!(WriteMode::Decimal.width_unit(), 8);
+	}
+
 	#[test]
 	fn read_decimal_tests() {
 		assert_eq!(read(&"0".to_string(), ReadMode::Decimal, WriteMode::Decimal, WriteLength::Unfixed, false), Ok(bitvec_of_num(0)));
@@ -1163,4 +2090,256 @@ mod tests {
 	
 		assert_eq!(convert(&"abcd".to_string(), ReadMode::Hex, WriteMode::Hex(false), WriteLength::Unfixed, &mut WriteSeparator::Separator("hey".to_string()), false, false), Ok("abheycd".to_string()));
 	}
+
+	#[test]
+	fn transform_tests() {
+		assert_eq!(crate::convert(&"0x10".to_string(), &mut Settings { read_mode: ReadMode::Interpret, write_mode: WriteMode::Hex(true), write_length: WriteLength::Unfixed, write_separator: WriteSeparator::None, signed_mode: false, write_prefix: false, transforms: vec![Transform::Set(BitRange::parse_list("7").unwrap())], ..Settings::default() }), Ok("90".to_string()));
+		assert_eq!(crate::convert(&"0".to_string(), &mut Settings { read_mode: ReadMode::Interpret, write_mode: WriteMode::Decimal, write_length: WriteLength::Fixed(1), write_separator: WriteSeparator::None, signed_mode: true, write_prefix: false, transforms: vec![Transform::Set(BitRange::parse_list("7").unwrap())], ..Settings::default() }), Ok("-128".to_string()));
+		assert_eq!(crate::convert(&"-1".to_string(), &mut Settings { read_mode: ReadMode::Interpret, write_mode: WriteMode::Decimal, write_length: WriteLength::Fixed(1), write_separator: WriteSeparator::None, signed_mode: true, write_prefix: false, transforms: vec![Transform::Clear(BitRange::parse_list("7").unwrap())], ..Settings::default() }), Ok("127".to_string()));
+
+		assert_eq!(crate::convert(&"0x81".to_string(), &mut Settings { read_mode: ReadMode::Interpret, write_mode: WriteMode::Hex(true), write_length: WriteLength::Unfixed, write_separator: WriteSeparator::None, signed_mode: false, write_prefix: false, transforms: vec![Transform::Clear(BitRange::parse_list("7").unwrap())], ..Settings::default() }), Ok("1".to_string()));
+		assert_eq!(crate::convert(&"0x81".to_string(), &mut Settings { read_mode: ReadMode::Interpret, write_mode: WriteMode::Hex(true), write_length: WriteLength::Fixed(2), write_separator: WriteSeparator::None, signed_mode: false, write_prefix: false, transforms: vec![Transform::Clear(BitRange::parse_list("7").unwrap())], ..Settings::default() }), Ok("0001".to_string()));
+
+		assert_eq!(crate::convert(&"0xDEADBEEF".to_string(), &mut Settings { read_mode: ReadMode::Interpret, write_mode: WriteMode::Hex(true), write_length: WriteLength::Unfixed, write_separator: WriteSeparator::None, signed_mode: false, write_prefix: false, transforms: vec![Transform::Extract(BitRange::parse("15:8").unwrap())], ..Settings::default() }), Ok("BE".to_string()));
+		assert!(crate::convert(&"0".to_string(), &mut Settings { read_mode: ReadMode::Interpret, write_mode: WriteMode::Hex(true), write_length: WriteLength::Fixed(1), write_separator: WriteSeparator::None, signed_mode: false, write_prefix: false, transforms: vec![Transform::Set(BitRange::parse_list("8").unwrap())], ..Settings::default() }).is_err());
+	}
+
+	#[test]
+	fn byte_order_tests() {
+		assert_eq!(crate::convert(&"78 56 34 12".to_string(), &mut Settings { read_mode: ReadMode::Hex, write_mode: WriteMode::Hex(true), write_length: WriteLength::Unfixed, write_separator: WriteSeparator::None, signed_mode: false, write_prefix: true, transforms: vec![Transform::ByteSwap], ..Settings::default() }), Ok("0x12345678".to_string()));
+		assert_eq!(crate::convert(&"0x12345678".to_string(), &mut Settings { read_mode: ReadMode::Interpret, write_mode: WriteMode::Hex(true), write_length: WriteLength::Unfixed, write_separator: WriteSeparator::RuntimeDetermine, signed_mode: false, write_prefix: true, transforms: vec![Transform::ByteSwap], ..Settings::default() }), Ok("0x78 56 34 12".to_string()));
+		assert_eq!(crate::convert(&"0x1234".to_string(), &mut Settings { read_mode: ReadMode::Interpret, write_mode: WriteMode::Hex(true), write_length: WriteLength::Fixed(4), write_separator: WriteSeparator::None, signed_mode: false, write_prefix: true, transforms: vec![Transform::ByteSwap], ..Settings::default() }), Ok("0x34120000".to_string()));
+		assert_eq!(crate::convert(&"0x12345678".to_string(), &mut Settings { read_mode: ReadMode::Interpret, write_mode: WriteMode::Hex(true), write_length: WriteLength::Unfixed, write_separator: WriteSeparator::None, signed_mode: false, write_prefix: true, transforms: vec![Transform::WordSwap(16)], ..Settings::default() }), Ok("0x56781234".to_string()));
+		assert_eq!(crate::convert(&"0x0A0B0C0D".to_string(), &mut Settings { read_mode: ReadMode::Interpret, write_mode: WriteMode::Hex(true), write_length: WriteLength::Unfixed, write_separator: WriteSeparator::None, signed_mode: false, write_prefix: true, transforms: vec![Transform::Pdp], ..Settings::default() }), Ok("0x0B0A0D0C".to_string()));
+		assert!(crate::convert(&"5".to_string(), &mut Settings { read_mode: ReadMode::Interpret, write_mode: WriteMode::Octal, write_length: WriteLength::Fixed(1), write_separator: WriteSeparator::None, signed_mode: false, write_prefix: true, transforms: vec![Transform::ByteSwap], ..Settings::default() }).is_err());
+		assert!(crate::convert(&"0x12_34 56".to_string(), &mut Settings { read_mode: ReadMode::Interpret, write_mode: WriteMode::Hex(true), write_length: WriteLength::Unfixed, write_separator: WriteSeparator::None, signed_mode: false, write_prefix: true, transforms: vec![Transform::ByteSwap], ..Settings::default() }).is_err());
+		assert!(convert(&"1,000".to_string(), ReadMode::Hex, WriteMode::Hex(true), WriteLength::Unfixed, &mut WriteSeparator::None, false, true).is_err());
+		assert!(convert(&"12 34".to_string(), ReadMode::Hex, WriteMode::Hex(true), WriteLength::Unfixed, &mut WriteSeparator::None, false, true).is_err());
+	}
+
+	#[test]
+	fn permutation_tests() {
+		assert_eq!(crate::convert(&"1".to_string(), &mut Settings { read_mode: ReadMode::Interpret, write_mode: WriteMode::Hex(true), write_length: WriteLength::Fixed(2), write_separator: WriteSeparator::None, signed_mode: false, write_prefix: true, transforms: vec![Transform::Reverse], ..Settings::default() }), Ok("0x8000".to_string()));
+		assert_eq!(crate::convert(&"0x81".to_string(), &mut Settings { read_mode: ReadMode::Interpret, write_mode: WriteMode::Binary, write_length: WriteLength::Unfixed, write_separator: WriteSeparator::None, signed_mode: false, write_prefix: true, transforms: vec![Transform::RotateLeft(1)], ..Settings::default() }), Ok("0b11".to_string()));
+		assert_eq!(crate::convert(&"5".to_string(), &mut Settings { read_mode: ReadMode::Interpret, write_mode: WriteMode::Decimal, write_length: WriteLength::Unfixed, write_separator: WriteSeparator::None, signed_mode: false, write_prefix: false, transforms: vec![Transform::Reverse], ..Settings::default() }), Ok("160".to_string()));
+		assert_eq!(crate::convert(&"0x1".to_string(), &mut Settings { read_mode: ReadMode::Interpret, write_mode: WriteMode::Hex(true), write_length: WriteLength::Unfixed, write_separator: WriteSeparator::None, signed_mode: false, write_prefix: true, transforms: vec![Transform::Reverse], ..Settings::default() }), Ok("0x80".to_string()));
+		assert_eq!(crate::convert(&"0x1234".to_string(), &mut Settings { read_mode: ReadMode::Interpret, write_mode: WriteMode::Hex(true), write_length: WriteLength::Unfixed, write_separator: WriteSeparator::None, signed_mode: false, write_prefix: true, transforms: vec![Transform::parse_mask("0xF0F0", false).unwrap()], ..Settings::default() }), Ok("0x13".to_string()));
+		assert_eq!(crate::convert(&"7".to_string(), &mut Settings { read_mode: ReadMode::Decimal, write_mode: WriteMode::Binary, write_length: WriteLength::Fixed(1), write_separator: WriteSeparator::None, signed_mode: false, write_prefix: true, transforms: vec![Transform::GrayEncode], ..Settings::default() }), Ok("0b00000100".to_string()));
+		assert_eq!(crate::convert(&"0b00000100".to_string(), &mut Settings { read_mode: ReadMode::Interpret, write_mode: WriteMode::Decimal, write_length: WriteLength::Unfixed, write_separator: WriteSeparator::None, signed_mode: false, write_prefix: true, transforms: vec![Transform::GrayDecode], ..Settings::default() }), Ok("7".to_string()));
+	}
+
+	#[test]
+	fn cast_tests() {
+		assert_eq!(crate::convert(&"0xF80".to_string(), &mut Settings { read_mode: ReadMode::Interpret, write_mode: WriteMode::Decimal, write_length: WriteLength::Unfixed, write_separator: WriteSeparator::None, signed_mode: true, write_prefix: false, transforms: vec![Transform::parse_extend("12:32", true).unwrap()], ..Settings::default() }), Ok("-128".to_string()));
+		assert_eq!(crate::convert(&"0xF80".to_string(), &mut Settings { read_mode: ReadMode::Interpret, write_mode: WriteMode::Hex(true), write_length: WriteLength::Unfixed, write_separator: WriteSeparator::None, signed_mode: false, write_prefix: true, transforms: vec![Transform::parse_extend("12:32", true).unwrap()], ..Settings::default() }), Ok("0xFFFFFF80".to_string()));
+		assert_eq!(crate::convert(&"-2".to_string(), &mut Settings { read_mode: ReadMode::Interpret, write_mode: WriteMode::Hex(true), write_length: WriteLength::Fixed(8), write_separator: WriteSeparator::None, signed_mode: true, write_prefix: true, transforms: vec![Transform::parse_truncate("16").unwrap()], ..Settings::default() }), Ok("0x000000000000FFFE".to_string()));
+		assert_eq!(crate::convert(&"-2".to_string(), &mut Settings { read_mode: ReadMode::Interpret, write_mode: WriteMode::Decimal, write_length: WriteLength::Unfixed, write_separator: WriteSeparator::None, signed_mode: true, write_prefix: false, transforms: vec![Transform::parse_extend("8:16", false).unwrap()], ..Settings::default() }), Ok("254".to_string()));
+		// unfixed widths keep the width cast to, even when its high bits are zero
+		assert_eq!(crate::convert(&"0x1FF".to_string(), &mut Settings { read_mode: ReadMode::Interpret, write_mode: WriteMode::Hex(true), write_length: WriteLength::Unfixed, write_separator: WriteSeparator::None, signed_mode: false, write_prefix: true, transforms: vec![Transform::parse_extend("8:64", false).unwrap()], ..Settings::default() }), Ok("0x00000000000000FF".to_string()));
+		assert_eq!(crate::convert(&"0x1FF".to_string(), &mut Settings { read_mode: ReadMode::Interpret, write_mode: WriteMode::Binary, write_length: WriteLength::Unfixed, write_separator: WriteSeparator::None, signed_mode: false, write_prefix: true, transforms: vec![Transform::parse_extend("8:16", false).unwrap()], ..Settings::default() }), Ok("0b0000000011111111".to_string()));
+	}
+
+	#[test]
+	fn fixed_point_tests() {
+		let q15 = QFormat::parse("1.15", Rounding::Nearest, false).unwrap();
+		assert_eq!(convert(&"0.7071".to_string(), ReadMode::FixedPoint(q15), WriteMode::Hex(true), WriteLength::Unfixed, &mut WriteSeparator::None, true, true), Ok("0x5A82".to_string()));
+		assert_eq!(convert(&"-0.5".to_string(), ReadMode::FixedPoint(q15), WriteMode::Hex(true), WriteLength::Unfixed, &mut WriteSeparator::None, true, true), Ok("0xC000".to_string()));
+		assert_eq!(convert(&"-0.5".to_string(), ReadMode::FixedPoint(q15), WriteMode::Hex(true), WriteLength::Fixed(4), &mut WriteSeparator::None, true, true), Ok("0xFFFFC000".to_string()));
+		assert_eq!(convert(&"0x5A82".to_string(), ReadMode::Interpret, WriteMode::FixedPoint(q15), WriteLength::Unfixed, &mut WriteSeparator::None, true, true), Ok("0.70709228515625".to_string()));
+		assert_eq!(convert(&"0xC000".to_string(), ReadMode::Interpret, WriteMode::FixedPoint(q15), WriteLength::Unfixed, &mut WriteSeparator::None, true, true), Ok("-0.5".to_string()));
+		assert_eq!(convert(&"0xC000".to_string(), ReadMode::Interpret, WriteMode::FixedPoint(q15), WriteLength::Fixed(4), &mut WriteSeparator::None, true, true), Ok("-0.5".to_string()));
+		assert!(convert(&"-0.5".to_string(), ReadMode::FixedPoint(q15), WriteMode::Hex(true), WriteLength::Unfixed, &mut WriteSeparator::None, false, true).is_err());
+
+		let q31 = QFormat::parse("1.31", Rounding::Truncate, true).unwrap();
+		assert_eq!(convert(&"0.1".to_string(), ReadMode::FixedPoint(q31), WriteMode::Hex(true), WriteLength::Unfixed, &mut WriteSeparator::None, true, true), Ok("0x0CCCCCCC error=-0.00000000037252902984619140625".to_string()));
+	}
+
+	#[test]
+	fn fraction_tests() {
+		assert_eq!(convert(&"0.1".to_string(), ReadMode::Interpret, WriteMode::Binary, WriteLength::Unfixed, &mut WriteSeparator::None, false, true), Ok("0b0.0(0011)".to_string()));
+		assert_eq!(convert(&"0x0.8".to_string(), ReadMode::Interpret, WriteMode::Decimal, WriteLength::Unfixed, &mut WriteSeparator::None, false, true), Ok("0.5".to_string()));
+		assert_eq!(convert(&"-12.75".to_string(), ReadMode::Interpret, WriteMode::Hex(true), WriteLength::Fixed(1), &mut WriteSeparator::None, true, true), Ok("-0x0C.C".to_string()));
+		assert_eq!(convert(&"1.0".to_string(), ReadMode::Interpret, WriteMode::Hex(true), WriteLength::Unfixed, &mut WriteSeparator::None, false, true), Ok("0x1".to_string()));
+		assert_eq!(crate::convert(&".1".to_string(), &mut Settings { read_mode: ReadMode::Decimal, write_mode: WriteMode::Binary, write_length: WriteLength::Unfixed, write_separator: WriteSeparator::None, signed_mode: false, write_prefix: false, max_frac_digits: 3, ..Settings::default() }), Ok("0.000...".to_string()));
+		assert_eq!(crate::convert(&"0.1".to_string(), &mut Settings { read_mode: ReadMode::Decimal, write_mode: WriteMode::Hex(true), write_length: WriteLength::Unfixed, write_separator: WriteSeparator::None, signed_mode: false, write_prefix: true, max_frac_digits: 0, ..Settings::default() }), Ok("0x0...".to_string()));
+		assert_eq!(convert(&"11.01".to_string(), ReadMode::Binary, WriteMode::Decimal, WriteLength::Unfixed, &mut WriteSeparator::None, false, false), Ok("3.25".to_string()));
+		assert!(convert(&".".to_string(), ReadMode::Hex, WriteMode::Hex(true), WriteLength::Unfixed, &mut WriteSeparator::None, false, true).is_err());
+		assert!(convert(&"0x.".to_string(), ReadMode::Interpret, WriteMode::Hex(true), WriteLength::Unfixed, &mut WriteSeparator::None, false, true).is_err());
+		assert!(convert(&"0x1.G".to_string(), ReadMode::Interpret, WriteMode::Decimal, WriteLength::Unfixed, &mut WriteSeparator::None, false, true).is_err());
+		assert!(convert(&"1.5".to_string(), ReadMode::Interpret, WriteMode::Stats, WriteLength::Unfixed, &mut WriteSeparator::None, false, true).is_err());
+	}
+
+	#[test]
+	fn hex_float_tests() {
+		assert_eq!(convert(&"0x1.921fb6p+1".to_string(), ReadMode::Interpret, WriteMode::Hex(true), WriteLength::Unfixed, &mut WriteSeparator::None, false, true), Ok("0x400921FB60000000".to_string()));
+		assert_eq!(convert(&"0x1.921fb6p+1".to_string(), ReadMode::Float(float::F32), WriteMode::Hex(true), WriteLength::Unfixed, &mut WriteSeparator::None, false, true), Ok("0x40490FDB".to_string()));
+		assert_eq!(convert(&"-0x1p-1".to_string(), ReadMode::Float(float::F32), WriteMode::Binary, WriteLength::Unfixed, &mut WriteSeparator::None, false, true), Ok("0b10111111000000000000000000000000".to_string()));
+		assert_eq!(convert(&"0x3F800000".to_string(), ReadMode::Interpret, WriteMode::HexFloat(float::F32), WriteLength::Unfixed, &mut WriteSeparator::None, false, true), Ok("0x1p+0".to_string()));
+		assert_eq!(convert(&"0x400921FB54442D18".to_string(), ReadMode::Interpret, WriteMode::HexFloat(float::F64), WriteLength::Unfixed, &mut WriteSeparator::None, false, true), Ok("0x1.921fb54442d18p+1".to_string()));
+		assert!(convert(&"0x400921FB54442D18".to_string(), ReadMode::Interpret, WriteMode::HexFloat(float::F32), WriteLength::Unfixed, &mut WriteSeparator::None, false, true).is_err());
+	}
+
+	#[test]
+	fn ml_float_tests() {
+		assert_eq!(convert(&"-1.5".to_string(), ReadMode::Float(float::BF16), WriteMode::Hex(true), WriteLength::Unfixed, &mut WriteSeparator::None, false, true), Ok("0xBFC0".to_string()));
+		assert_eq!(convert(&"448".to_string(), ReadMode::Float(float::E4M3), WriteMode::Hex(true), WriteLength::Unfixed, &mut WriteSeparator::None, false, true), Ok("0x7E".to_string()));
+		assert_eq!(convert(&"0x3C00".to_string(), ReadMode::Interpret, WriteMode::FloatFields(float::F16), WriteLength::Unfixed, &mut WriteSeparator::None, false, true), Ok("value=1 class=normal sign=0 exponent=0b01111 (2^0) mantissa=0b0000000000".to_string()));
+		assert_eq!(convert(&"0x1".to_string(), ReadMode::Interpret, WriteMode::FloatFields(float::E4M3), WriteLength::Unfixed, &mut WriteSeparator::None, false, true), Ok("value=0.001953125 class=subnormal sign=0 exponent=0b0000 (2^-6) mantissa=0b001".to_string()));
+		assert!(convert(&"0x1FF".to_string(), ReadMode::Interpret, WriteMode::FloatFields(float::E5M2), WriteLength::Unfixed, &mut WriteSeparator::None, false, true).is_err());
+	}
+
+	#[test]
+	fn bcd_tests() {
+		assert_eq!(convert(&"1234".to_string(), ReadMode::Decimal, WriteMode::Bcd(BcdKind::Packed), WriteLength::Unfixed, &mut WriteSeparator::None, false, true), Ok("0x1234".to_string()));
+		assert_eq!(convert(&"-1234".to_string(), ReadMode::Decimal, WriteMode::Bcd(BcdKind::Zoned), WriteLength::Fixed(4), &mut WriteSeparator::None, true, true), Ok("0xF1F2F3D4".to_string()));
+		assert!(convert(&"-12".to_string(), ReadMode::Decimal, WriteMode::Bcd(BcdKind::Unpacked), WriteLength::Fixed(4), &mut WriteSeparator::None, true, true).is_err());
+		assert_eq!(convert(&"12".to_string(), ReadMode::Decimal, WriteMode::Bcd(BcdKind::Packed), WriteLength::Fixed(4), &mut WriteSeparator::None, false, true), Ok("0x00000012".to_string()));
+		assert_eq!(convert(&"100".to_string(), ReadMode::Decimal, WriteMode::Bcd(BcdKind::Packed), WriteLength::Fixed(1), &mut WriteSeparator::None, true, true), Err("Number unrepresentable in fixed width".to_string()));
+		assert_eq!(convert(&"0x1234".to_string(), ReadMode::Bcd(BcdKind::Packed), WriteMode::Hex(true), WriteLength::Unfixed, &mut WriteSeparator::None, false, true), Ok("0x4D2".to_string()));
+		assert_eq!(convert(&"F1F2F3D4".to_string(), ReadMode::Bcd(BcdKind::Zoned), WriteMode::Hex(true), WriteLength::Fixed(4), &mut WriteSeparator::None, true, true), Ok("0xFFFFFB2E".to_string()));
+		assert!(convert(&"F1F2F3D4".to_string(), ReadMode::Bcd(BcdKind::Zoned), WriteMode::Hex(true), WriteLength::Fixed(4), &mut WriteSeparator::None, false, true).is_err());
+		assert!(convert(&"0x12A4".to_string(), ReadMode::Bcd(BcdKind::Packed), WriteMode::Hex(true), WriteLength::Unfixed, &mut WriteSeparator::None, false, true).is_err());
+	}
+
+	#[test]
+	fn base_encoding_tests() {
+		let base64 = BaseEncoding::parse("base64").unwrap();
+		let base58 = BaseEncoding::parse("base58:zeros").unwrap();
+		assert_eq!(convert(&"65537".to_string(), ReadMode::Decimal, WriteMode::BaseEncoded(base64), WriteLength::Unfixed, &mut WriteSeparator::RuntimeDetermine, false, true), Ok("AQAB".to_string()));
+		assert_eq!(convert(&"65537".to_string(), ReadMode::Decimal, WriteMode::BaseEncoded(base64), WriteLength::Fixed(4), &mut WriteSeparator::None, false, true), Ok("AQAB".to_string()));
+		assert_eq!(convert(&"0x287FB4CD".to_string(), ReadMode::Interpret, WriteMode::BaseEncoded(base58), WriteLength::Fixed(6), &mut WriteSeparator::None, false, true), Ok("11233QC4".to_string()));
+		assert_eq!(convert(&"AQAB".to_string(), ReadMode::BaseEncoded(base64), WriteMode::Decimal, WriteLength::Unfixed, &mut WriteSeparator::None, false, true), Ok("65537".to_string()));
+		assert_eq!(convert(&"-_8".to_string(), ReadMode::BaseEncoded(BaseEncoding::parse("base64url").unwrap()), WriteMode::Hex(true), WriteLength::Unfixed, &mut WriteSeparator::None, false, true), Ok("0xFBFF".to_string()));
+		assert_eq!(convert(&"-1".to_string(), ReadMode::Decimal, WriteMode::BaseEncoded(base64), WriteLength::Fixed(2), &mut WriteSeparator::None, true, true), Ok("//8=".to_string()));
+		assert!(convert(&"A*".to_string(), ReadMode::BaseEncoded(base64), WriteMode::Decimal, WriteLength::Unfixed, &mut WriteSeparator::None, false, true).is_err());
+	}
+
+	#[test]
+	fn varint_tests() {
+		assert_eq!(convert(&"624485".to_string(), ReadMode::Decimal, WriteMode::Varint(Varint::Uleb128), WriteLength::Unfixed, &mut WriteSeparator::RuntimeDetermine, false, true), Ok("0xE5 8E 26".to_string()));
+		assert_eq!(convert(&"-123456".to_string(), ReadMode::Decimal, WriteMode::Varint(Varint::Sleb128), WriteLength::Fixed(4), &mut WriteSeparator::None, true, true), Ok("0xC0BB78".to_string()));
+		assert_eq!(convert(&"-2".to_string(), ReadMode::Decimal, WriteMode::Varint(Varint::Protobuf(true)), WriteLength::Fixed(8), &mut WriteSeparator::None, true, true), Ok("0x03".to_string()));
+		assert_eq!(convert(&"0xE5 8E 26".to_string(), ReadMode::Varint(Varint::Uleb128), WriteMode::Decimal, WriteLength::Unfixed, &mut WriteSeparator::None, false, true), Ok("624485".to_string()));
+		assert_eq!(convert(&"C0BB78".to_string(), ReadMode::Varint(Varint::Sleb128), WriteMode::Hex(true), WriteLength::Fixed(4), &mut WriteSeparator::None, true, true), Ok("0xFFFE1DC0".to_string()));
+		assert_eq!(convert(&"FFFFFFFFFFFFFFFFFF01".to_string(), ReadMode::Varint(Varint::Protobuf(false)), WriteMode::Hex(true), WriteLength::Fixed(8), &mut WriteSeparator::None, true, true), Ok("0xFFFFFFFFFFFFFFFF".to_string()));
+		assert_eq!(convert(&"7BBD".to_string(), ReadMode::Varint(Varint::Quic), WriteMode::Varint(Varint::Vlq), WriteLength::Unfixed, &mut WriteSeparator::None, false, true), Ok("0xF73D".to_string()));
+		assert!(convert(&"-1".to_string(), ReadMode::Decimal, WriteMode::Varint(Varint::Uleb128), WriteLength::Fixed(1), &mut WriteSeparator::None, true, true).is_err());
+		assert!(convert(&"C0BB78".to_string(), ReadMode::Varint(Varint::Sleb128), WriteMode::Hex(true), WriteLength::Fixed(4), &mut WriteSeparator::None, false, true).is_err());
+	}
+
+	#[test]
+	fn item_tests() {
+		assert_eq!(convert(&"1000".to_string(), ReadMode::Decimal, WriteMode::Item(ItemFormat::Cbor), WriteLength::Unfixed, &mut WriteSeparator::None, false, true), Ok("0x1903E8".to_string()));
+		assert_eq!(convert(&"-33".to_string(), ReadMode::Decimal, WriteMode::ItemFields(ItemFormat::MessagePack), WriteLength::Fixed(2), &mut WriteSeparator::RuntimeDetermine, true, true), Ok("0xD0 DF header=0xD0 (int 8) argument=0xDF value=-33".to_string()));
+		assert_eq!(convert(&"0x39 03 E7".to_string(), ReadMode::Item(ItemFormat::Cbor), WriteMode::Hex(true), WriteLength::Fixed(2), &mut WriteSeparator::None, true, true), Ok("0xFC18".to_string()));
+		assert_eq!(convert(&"CE00011170".to_string(), ReadMode::Item(ItemFormat::MessagePack), WriteMode::Decimal, WriteLength::Unfixed, &mut WriteSeparator::None, false, true), Ok("70000".to_string()));
+		assert!(convert(&"20".to_string(), ReadMode::Item(ItemFormat::Cbor), WriteMode::Decimal, WriteLength::Unfixed, &mut WriteSeparator::None, false, true).is_err());
+		assert!(convert(&"0x10000000000000000".to_string(), ReadMode::Interpret, WriteMode::Item(ItemFormat::Cbor), WriteLength::Unfixed, &mut WriteSeparator::None, false, true).is_err());
+	}
+
+	#[test]
+	fn der_tests() {
+		assert_eq!(convert(&"128".to_string(), ReadMode::Decimal, WriteMode::DerInteger, WriteLength::Unfixed, &mut WriteSeparator::RuntimeDetermine, false, true), Ok("0x02 02 00 80".to_string()));
+		assert_eq!(convert(&"-129".to_string(), ReadMode::Decimal, WriteMode::DerInteger, WriteLength::Fixed(4), &mut WriteSeparator::None, true, true), Ok("0x0202FF7F".to_string()));
+		assert_eq!(convert(&"02 03 01 00 01".to_string(), ReadMode::DerInteger, WriteMode::Decimal, WriteLength::Unfixed, &mut WriteSeparator::None, false, true), Ok("65537".to_string()));
+		assert_eq!(convert(&"0x0202FF7F".to_string(), ReadMode::DerInteger, WriteMode::Hex(true), WriteLength::Fixed(2), &mut WriteSeparator::None, true, true), Ok("0xFF7F".to_string()));
+		assert!(convert(&"0x0202FF7F".to_string(), ReadMode::DerInteger, WriteMode::Hex(true), WriteLength::Fixed(2), &mut WriteSeparator::None, false, true).is_err());
+		assert_eq!(convert(&"1.2.840.113549".to_string(), ReadMode::Oid, WriteMode::Hex(true), WriteLength::Unfixed, &mut WriteSeparator::None, false, true), Ok("0x06062A864886F70D".to_string()));
+		assert_eq!(convert(&"0x06062A864886F70D".to_string(), ReadMode::Interpret, WriteMode::Oid, WriteLength::Fixed(10), &mut WriteSeparator::None, false, true), Ok("1.2.840.113549".to_string()));
+		assert!(convert(&"0x0201FF".to_string(), ReadMode::Interpret, WriteMode::Oid, WriteLength::Unfixed, &mut WriteSeparator::None, false, true).is_err());
+	}
+
+	#[test]
+	fn protobuf_tests() {
+		assert_eq!(convert(&"08 96 01 12 07 74 65 73 74 69 6E 67".to_string(), ReadMode::Hex, WriteMode::Protobuf, WriteLength::Unfixed, &mut WriteSeparator::None, false, true), Ok("1: varint 150 (sint 75)\n2: len 7 string \"testing\"".to_string()));
+		assert_eq!(convert(&"0x1A03089601".to_string(), ReadMode::Interpret, WriteMode::Protobuf, WriteLength::Fixed(8), &mut WriteSeparator::None, false, true), Ok("3: len 3 message\n  1: varint 150 (sint 75)".to_string()));
+		assert!(convert(&"0x1A0508".to_string(), ReadMode::Interpret, WriteMode::Protobuf, WriteLength::Unfixed, &mut WriteSeparator::None, false, true).is_err());
+	}
+
+	#[test]
+	fn unicode_tests() {
+		assert_eq!(convert(&"U+1F600".to_string(), ReadMode::CodePoint, WriteMode::Utf(Utf::Utf8), WriteLength::Unfixed, &mut WriteSeparator::RuntimeDetermine, false, true), Ok("0xF0 9F 98 80".to_string()));
+		assert_eq!(convert(&"U+1F600".to_string(), ReadMode::CodePoint, WriteMode::Utf(Utf::Utf16(false)), WriteLength::Unfixed, &mut WriteSeparator::RuntimeDetermine, false, true), Ok("0xD83D DE00".to_string()));
+		assert_eq!(convert(&"\\uD83D\\uDE00".to_string(), ReadMode::CodePoint, WriteMode::Utf(Utf::Utf16(true)), WriteLength::Unfixed, &mut WriteSeparator::None, false, true), Ok("0x3DD800DE".to_string()));
+		assert_eq!(convert(&"-".to_string(), ReadMode::CodePoint, WriteMode::Decimal, WriteLength::Unfixed, &mut WriteSeparator::None, false, true), Ok("45".to_string()));
+		assert_eq!(convert(&"0xF0 9F 98 80".to_string(), ReadMode::Utf(Utf::Utf8), WriteMode::CodePoint, WriteLength::Unfixed, &mut WriteSeparator::None, false, true), Ok("U+1F600".to_string()));
+		assert_eq!(convert(&"é".to_string(), ReadMode::CodePoint, WriteMode::UtfFields(Utf::Utf8), WriteLength::Fixed(4), &mut WriteSeparator::None, false, true), Ok("0xC3A9 U+00E9 name=LATIN SMALL LETTER E WITH ACUTE category=Ll".to_string()));
+		assert!(convert(&"0xD800".to_string(), ReadMode::Hex, WriteMode::Utf(Utf::Utf8), WriteLength::Unfixed, &mut WriteSeparator::None, false, true).is_err());
+		assert!(convert(&"0x110000".to_string(), ReadMode::Hex, WriteMode::CodePoint, WriteLength::Unfixed, &mut WriteSeparator::None, false, true).is_err());
+	}
+
+	#[test]
+	fn text_tests() {
+		assert_eq!(convert(&"Hello\\n".to_string(), ReadMode::Text(Encoding::Utf8), WriteMode::Hex(true), WriteLength::Unfixed, &mut WriteSeparator::RuntimeDetermine, false, false), Ok("48 65 6C 6C 6F 0A".to_string()));
+		assert_eq!(convert(&"\\0A".to_string(), ReadMode::Text(Encoding::Ascii), WriteMode::Hex(true), WriteLength::Unfixed, &mut WriteSeparator::None, false, true), Ok("0x0041".to_string()));
+		assert_eq!(convert(&"A, B".to_string(), ReadMode::Text(Encoding::Cp037), WriteMode::Hex(true), WriteLength::Unfixed, &mut WriteSeparator::None, false, true), Ok("0xC16B40C2".to_string()));
+		assert_eq!(convert(&"0x48690AFF".to_string(), ReadMode::Interpret, WriteMode::Text(TextFormat::parse("ascii:python").unwrap()), WriteLength::Unfixed, &mut WriteSeparator::None, false, true), Ok("Hi\\n\\xFF".to_string()));
+		assert_eq!(convert(&"0x41".to_string(), ReadMode::Interpret, WriteMode::Text(TextFormat::parse("utf8").unwrap()), WriteLength::Fixed(2), &mut WriteSeparator::None, false, true), Ok("\\000A".to_string()));
+		assert!(convert(&"".to_string(), ReadMode::Text(Encoding::Utf8), WriteMode::Hex(true), WriteLength::Unfixed, &mut WriteSeparator::None, false, true).is_err());
+	}
+
+	#[test]
+	fn char_literal_tests() {
+		assert_eq!(convert(&"'A'".to_string(), ReadMode::Interpret, WriteMode::Decimal, WriteLength::Unfixed, &mut WriteSeparator::None, false, true), Ok("65".to_string()));
+		assert_eq!(convert(&"b'\\x7f'".to_string(), ReadMode::Interpret, WriteMode::Hex(true), WriteLength::Unfixed, &mut WriteSeparator::None, false, true), Ok("0x7F".to_string()));
+		assert_eq!(convert(&"' '".to_string(), ReadMode::Interpret, WriteMode::Decimal, WriteLength::Unfixed, &mut WriteSeparator::None, false, true), Ok("32".to_string()));
+		assert_eq!(convert(&"'.'".to_string(), ReadMode::Interpret, WriteMode::Decimal, WriteLength::Unfixed, &mut WriteSeparator::None, false, true), Ok("46".to_string()));
+		assert_eq!(convert(&"'\\0ABC'".to_string(), ReadMode::Interpret, WriteMode::Hex(true), WriteLength::Unfixed, &mut WriteSeparator::None, false, true), Ok("0x00414243".to_string()));
+		assert_eq!(convert(&"0x52494646".to_string(), ReadMode::Interpret, WriteMode::CharLiteral, WriteLength::Unfixed, &mut WriteSeparator::None, false, true), Ok("'RIFF'".to_string()));
+		assert_eq!(convert(&"'mp4a'".to_string(), ReadMode::Interpret, WriteMode::CharLiteral, WriteLength::Unfixed, &mut WriteSeparator::None, false, true), Ok("'mp4a'".to_string()));
+		assert_eq!(convert(&"'aé'".to_string(), ReadMode::Interpret, WriteMode::Hex(true), WriteLength::Unfixed, &mut WriteSeparator::None, false, true), Ok("0x61C3A9".to_string()));
+		assert!(convert(&"-'A'".to_string(), ReadMode::Interpret, WriteMode::Decimal, WriteLength::Unfixed, &mut WriteSeparator::None, true, true).is_err());
+	}
+
+	#[test]
+	fn roman_tests() {
+		assert_eq!(convert(&"0rXLII".to_string(), ReadMode::Interpret, WriteMode::Decimal, WriteLength::Unfixed, &mut WriteSeparator::None, false, true), Ok("42".to_string()));
+		assert_eq!(convert(&"MCMXCIV".to_string(), ReadMode::Roman(false), WriteMode::Hex(true), WriteLength::Unfixed, &mut WriteSeparator::None, false, true), Ok("0x7CA".to_string()));
+		assert_eq!(convert(&"IIII".to_string(), ReadMode::Roman(true), WriteMode::Roman(RomanStyle::Standard), WriteLength::Unfixed, &mut WriteSeparator::None, false, true), Ok("IV".to_string()));
+		assert_eq!(convert(&"5000".to_string(), ReadMode::Decimal, WriteMode::Roman(RomanStyle::Vinculum), WriteLength::Unfixed, &mut WriteSeparator::None, false, true), Ok("V\u{305}".to_string()));
+		assert!(convert(&"IIII".to_string(), ReadMode::Roman(false), WriteMode::Decimal, WriteLength::Unfixed, &mut WriteSeparator::None, false, true).is_err());
+		assert!(convert(&"4000".to_string(), ReadMode::Decimal, WriteMode::Roman(RomanStyle::Standard), WriteLength::Unfixed, &mut WriteSeparator::None, false, true).is_err());
+		assert!(convert(&"-5".to_string(), ReadMode::Decimal, WriteMode::Roman(RomanStyle::Vinculum), WriteLength::Fixed(1), &mut WriteSeparator::None, true, true).is_err());
+		assert_eq!(convert(&"0".to_string(), ReadMode::Decimal, WriteMode::Roman(RomanStyle::Standard), WriteLength::Fixed(1), &mut WriteSeparator::None, false, true), Err("Roman numerals have no zero".to_string()));
+	}
+
+	#[test]
+	fn radix_tests() {
+		assert_eq!(convert(&"11".to_string(), ReadMode::Radix(Radix::Negabinary), WriteMode::Hex(true), WriteLength::Fixed(1), &mut WriteSeparator::None, true, true), Ok("0xFF".to_string()));
+		assert_eq!(convert(&"-0+".to_string(), ReadMode::Radix(Radix::BalancedTernary(false)), WriteMode::Hex(true), WriteLength::Fixed(1), &mut WriteSeparator::None, true, true), Ok("0xF8".to_string()));
+		assert_eq!(convert(&"-15".to_string(), ReadMode::Decimal, WriteMode::Radix(Radix::Negadecimal), WriteLength::Fixed(1), &mut WriteSeparator::None, true, true), Ok("25".to_string()));
+		assert_eq!(convert(&"0xF8".to_string(), ReadMode::Interpret, WriteMode::Radix(Radix::BalancedTernary(true)), WriteLength::Fixed(1), &mut WriteSeparator::None, true, true), Ok("-0+".to_string()));
+		assert!(convert(&"11".to_string(), ReadMode::Radix(Radix::Negabinary), WriteMode::Hex(true), WriteLength::Fixed(1), &mut WriteSeparator::None, false, true).is_err());
+		assert_eq!(convert(&"463".to_string(), ReadMode::Decimal, WriteMode::Radix(Radix::Permutation(6)), WriteLength::Unfixed, &mut WriteSeparator::None, false, true), Ok("351042".to_string()));
+		assert_eq!(convert(&"0b101".to_string(), ReadMode::Radix(Radix::Zeckendorf), WriteMode::Radix(Radix::Factoradic), WriteLength::Unfixed, &mut WriteSeparator::None, false, true), Err("Character b not allowed in Zeckendorf".to_string()));
+		assert_eq!(convert(&"101".to_string(), ReadMode::Radix(Radix::Zeckendorf), WriteMode::Radix(Radix::Factoradic), WriteLength::Unfixed, &mut WriteSeparator::None, false, true), Ok("200".to_string()));
+		assert_eq!(convert(&"XFD".to_string(), ReadMode::Radix(Radix::Bijective(26, true)), WriteMode::Decimal, WriteLength::Unfixed, &mut WriteSeparator::None, false, true), Ok("16384".to_string()));
+		assert!(convert(&"0".to_string(), ReadMode::Decimal, WriteMode::Radix(Radix::Bijective(26, true)), WriteLength::Unfixed, &mut WriteSeparator::None, false, true).is_err());
+		assert!(convert(&"720".to_string(), ReadMode::Decimal, WriteMode::Radix(Radix::Permutation(6)), WriteLength::Unfixed, &mut WriteSeparator::None, false, true).is_err());
+		assert!(convert(&"-1".to_string(), ReadMode::Decimal, WriteMode::Radix(Radix::Zeckendorf), WriteLength::Fixed(1), &mut WriteSeparator::None, true, true).is_err());
+		assert!(is_ternary_number("--+", ReadMode::Radix(Radix::BalancedTernary(true))));
+		assert!(!is_ternary_number("--set", ReadMode::Radix(Radix::BalancedTernary(true))));
+		assert!(!is_ternary_number("--+", ReadMode::Decimal));
+		assert_eq!(convert(&"--+".to_string(), ReadMode::Radix(Radix::BalancedTernary(false)), WriteMode::Hex(true), WriteLength::Fixed(1), &mut WriteSeparator::None, true, true), Ok("0xF5".to_string()));
+		assert_eq!(convert(&"0".to_string(), ReadMode::Decimal, WriteMode::Radix(Radix::Negabinary), WriteLength::Fixed(1), &mut WriteSeparator::None, false, true), Ok("0".to_string()));
+		assert_eq!(convert(&"0".to_string(), ReadMode::Decimal, WriteMode::Radix(Radix::Factoradic), WriteLength::Fixed(1), &mut WriteSeparator::None, false, true), Ok("0".to_string()));
+		assert_eq!(convert(&"0".to_string(), ReadMode::Decimal, WriteMode::Radix(Radix::Zeckendorf), WriteLength::Fixed(1), &mut WriteSeparator::None, false, true), Ok("0".to_string()));
+		assert!(convert(&"0".to_string(), ReadMode::Decimal, WriteMode::Radix(Radix::Bijective(26, true)), WriteLength::Fixed(1), &mut WriteSeparator::None, false, true).is_err());
+	}
+
+	#[test]
+	fn dfp_tests() {
+		let d64_dpd = DecFormat::parse("decimal64:dpd").unwrap();
+		assert_eq!(convert(&"123.45".to_string(), ReadMode::Dfp(d64_dpd), WriteMode::Hex(true), WriteLength::Unfixed, &mut WriteSeparator::None, false, true), Ok("0x22300000000049C5".to_string()));
+		assert_eq!(convert(&"-1".to_string(), ReadMode::Dfp(dfp::DECIMAL32), WriteMode::Hex(true), WriteLength::Unfixed, &mut WriteSeparator::None, false, true), Ok("0xB2800001".to_string()));
+		assert_eq!(convert(&"0x22300000000049C5".to_string(), ReadMode::Interpret, WriteMode::Dfp(d64_dpd), WriteLength::Unfixed, &mut WriteSeparator::None, false, true), Ok("123.45".to_string()));
+		assert_eq!(convert(&"0x31C0000000000001".to_string(), ReadMode::Interpret, WriteMode::DfpFields(dfp::DECIMAL64), WriteLength::Unfixed, &mut WriteSeparator::None, false, true), Ok("value=1 class=finite sign=0 combination=0b01100 continuation=0b01110000 exponent=0 (biased 398) coefficient=1".to_string()));
+	}
+
+	#[test]
+	fn stats_tests() {
+		assert_eq!(convert(&"0x10".to_string(), ReadMode::Interpret, WriteMode::Stats, WriteLength::Fixed(4), &mut WriteSeparator::None, false, false), Ok("width=32 popcount=1 clz=27 ctz=4 highest=4 lowest=4 parity=odd pow2=true log2=4 next_pow2=0x10".to_string()));
+		assert_eq!(convert(&"0x10".to_string(), ReadMode::Interpret, WriteMode::Stats, WriteLength::Unfixed, &mut WriteSeparator::None, false, false), Ok("width=5 popcount=1 clz=0 ctz=4 highest=4 lowest=4 parity=odd pow2=true log2=4 next_pow2=0x10".to_string()));
+		assert_eq!(convert(&"-1".to_string(), ReadMode::Interpret, WriteMode::Stats, WriteLength::Fixed(2), &mut WriteSeparator::None, true, false), Ok("width=16 popcount=16 clz=0 ctz=0 highest=15 lowest=0 parity=even pow2=false log2=15 next_pow2=0x10000".to_string()));
+	}
 }
\ No newline at end of file
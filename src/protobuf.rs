@@ -0,0 +1,136 @@
+/// The deepest nesting of messages that is decoded, so a length-delimited field is never guessed into a deep tree
+const MAX_DEPTH: usize = 16;
+
+/// A field of a message, decoded without knowing its schema
+enum Value {
+	Varint(u64),
+	Fixed64(u64),
+	/// length-delimited bytes, with the fields they decode to if they look like a message
+	Len(Vec<u8>, Option<Vec<Field>>),
+	Fixed32(u32)
+}
+
+struct Field {
+	number: u64,
+	value: Value
+}
+
+fn read_varint(bytes: &[u8], pos: &mut usize) -> Result<u64, String> {
+	let mut value = 0u64;
+	for shift in (0..64).step_by(7) {
+		let byte = *bytes.get(*pos).ok_or("Message ends in the middle of a varint")?;
+		*pos += 1;
+		if shift == 63 && byte > 1 {
+			return Err("Varint is longer than 64 bits".to_string());
+		}
+		value |= ((byte & 0x7F) as u64) << shift;
+		if byte & 0x80 == 0 {
+			return Ok(value);
+		}
+	}
+	Err("Varint is longer than 64 bits".to_string())
+}
+
+fn take<'a>(bytes: &'a [u8], pos: &mut usize, len: usize) -> Result<&'a [u8], String> {
+	if bytes.len() - *pos < len {
+		return Err(format!("Field needs {} bytes but only {} are left", len, bytes.len() - *pos));
+	}
+	*pos += len;
+	Ok(&bytes[*pos - len..*pos])
+}
+
+/// Decodes the fields of a message, which must use all the bytes
+fn decode_fields(bytes: &[u8], depth: usize) -> Result<Vec<Field>, String> {
+	let mut fields = Vec::new();
+	let mut pos = 0;
+	while pos < bytes.len() {
+		let key = read_varint(bytes, &mut pos)?;
+		let number = key >> 3;
+		if number == 0 || number > 0x1FFF_FFFF {
+			return Err(format!("Field number {} is out of range", number));
+		}
+		let value = match key & 7 {
+			0 => Value::Varint(read_varint(bytes, &mut pos)?),
+			1 => Value::Fixed64(u64::from_le_bytes(take(bytes, &mut pos, 8)?.try_into().unwrap())),
+			2 => {
+				let len = read_varint(bytes, &mut pos)?;
+				let value = take(bytes, &mut pos, len.min(usize::MAX as u64) as usize)?.to_vec();
+				// guess that the bytes are a message if they decode as one
+				let message = if depth < MAX_DEPTH && !value.is_empty() { decode_fields(&value, depth + 1).ok() } else { None };
+				Value::Len(value, message)
+			}
+			5 => Value::Fixed32(u32::from_le_bytes(take(bytes, &mut pos, 4)?.try_into().unwrap())),
+			wire_type => return Err(format!("Wire type {} of field {} is not supported", wire_type, number))
+		};
+		fields.push(Field { number, value });
+	}
+	Ok(fields)
+}
+
+/// Gets the bytes as a string if they are UTF-8 without control characters
+fn as_text(bytes: &[u8]) -> Option<&str> {
+	std::str::from_utf8(bytes).ok().filter(|text| !text.chars().any(|c| c.is_control() && c != '\n' && c != '\t'))
+}
+
+fn write_fields(fields: &[Field], indent: usize, ret: &mut Vec<String>) {
+	let pad = "  ".repeat(indent);
+	for field in fields {
+		match &field.value {
+			Value::Varint(value) => {
+				let zigzag = ((value >> 1) as i64) ^ -((value & 1) as i64);
+				let int64 = if (*value as i64) < 0 { format!("int64 {}, ", *value as i64) } else { String::new() };
+				ret.push(format!("{}{}: varint {} ({}sint {})", pad, field.number, value, int64, zigzag));
+			}
+			Value::Fixed64(value) => {
+				ret.push(format!("{}{}: i64 0x{:016X} (int {}, double {})", pad, field.number, value, *value as i64, f64::from_bits(*value)));
+			}
+			Value::Fixed32(value) => {
+				ret.push(format!("{}{}: i32 0x{:08X} (int {}, float {})", pad, field.number, value, *value as i32, f32::from_bits(*value)));
+			}
+			Value::Len(bytes, Some(message)) => {
+				let text = as_text(bytes).map(|text| format!(" or string {:?}", text)).unwrap_or_default();
+				ret.push(format!("{}{}: len {} message{}", pad, field.number, bytes.len(), text));
+				write_fields(message, indent + 1, ret);
+			}
+			Value::Len(bytes, None) => match as_text(bytes) {
+				Some(text) => ret.push(format!("{}{}: len {} string {:?}", pad, field.number, bytes.len(), text)),
+				None => {
+					let hex: Vec<String> = bytes.iter().map(|b| format!("{:02X}", b)).collect();
+					ret.push(format!("{}{}: len {} bytes {}", pad, field.number, bytes.len(), hex.join(" ")));
+				}
+			}
+		}
+	}
+}
+
+/// Writes the fields of a message as a tree, one field per line, where each length-delimited field is shown as
+/// a nested message if it decodes as one, and otherwise as a string or bytes
+pub fn write_message(bytes: &[u8]) -> Result<String, String> {
+	if bytes.is_empty() {
+		return Err("Missing message bytes".to_string());
+	}
+	let mut ret = Vec::new();
+	write_fields(&decode_fields(bytes, 0)?, 0, &mut ret);
+	Ok(ret.join("\n"))
+}
+
+#[cfg(test)]
+mod tests {
+	use crate::protobuf::*;
+
+	#[test]
+	fn message_tests() {
+		assert_eq!(write_message(&[0x08, 0x96, 0x01]), Ok("1: varint 150 (sint 75)".to_string()));
+		assert_eq!(write_message(&[0x08, 0x03]), Ok("1: varint 3 (sint -2)".to_string()));
+		assert_eq!(write_message(&[0x08, 0xFF, 0xFF, 0xFF, 0xFF, 0xFF, 0xFF, 0xFF, 0xFF, 0xFF, 0x01]), Ok("1: varint 18446744073709551615 (int64 -1, sint -9223372036854775808)".to_string()));
+		assert_eq!(write_message(&[0x12, 0x07, b't', b'e', b's', b't', b'i', b'n', b'g']), Ok("2: len 7 string \"testing\"".to_string()));
+		assert_eq!(write_message(&[0x1A, 0x03, 0x08, 0x96, 0x01]), Ok("3: len 3 message\n  1: varint 150 (sint 75)".to_string()));
+		assert_eq!(write_message(&[0x1A, 0x02, 0xFF, 0x00]), Ok("3: len 2 bytes FF 00".to_string()));
+		assert_eq!(write_message(&[0x25, 0x00, 0x00, 0x80, 0x3F]), Ok("4: i32 0x3F800000 (int 1065353216, float 1)".to_string()));
+		assert_eq!(write_message(&[0x29, 0, 0, 0, 0, 0, 0, 0xF0, 0xBF]), Ok("5: i64 0xBFF0000000000000 (int -4616189618054758400, double -1)".to_string()));
+		assert!(write_message(&[0x08]).is_err());
+		assert!(write_message(&[0x12, 0x05, 0x00]).is_err());
+		assert!(write_message(&[0x0B]).is_err());
+		assert!(write_message(&[0x00, 0x01]).is_err());
+	}
+}
@@ -0,0 +1,460 @@
+use crate::decimal::Decimal;
+
+/// A number system other than the usual positional bases
+#[derive(PartialEq, Eq, Debug)]
+#[derive(Clone, Copy)]
+pub enum Radix {
+	/// base -2
+	Negabinary,
+	/// base -10
+	Negadecimal,
+	/// base 3 with the digits -1, 0 and 1
+	BalancedTernary(bool /* writes digits as -0+ instead of T01 */),
+	/// factorial base, where the digit at position i (from 0) is at most i and is worth i!
+	Factoradic,
+	/// the permutation of 0..n that the factoradic digits are the Lehmer code of
+	Permutation(usize /* n */),
+	/// sum of non-consecutive Fibonacci numbers, one bit per Fibonacci number from 1, 2, 3, 5, ...
+	Zeckendorf,
+	/// base k with the digits 1 to k and no zero, written 1-9 then A-Z, or A-Z for spreadsheet columns (A is 1, AA is 27)
+	Bijective(u32 /* k */, bool /* letters */)
+}
+
+/// The most elements of a permutation, where 1000! is already far more than 8000 bits
+const MAX_PERMUTATION: usize = 1000;
+
+impl Radix {
+	pub fn parse(param: &str) -> Result<Radix, String> {
+		match param {
+			"negabinary" | "-2" => Ok(Radix::Negabinary),
+			"negadecimal" | "-10" => Ok(Radix::Negadecimal),
+			"ternary" | "balanced-ternary" => Ok(Radix::BalancedTernary(false)),
+			"ternary-signs" | "balanced-ternary-signs" => Ok(Radix::BalancedTernary(true)),
+			"factoradic" => Ok(Radix::Factoradic),
+			"zeckendorf" => Ok(Radix::Zeckendorf),
+			"columns" | "spreadsheet" => Ok(Radix::Bijective(26, true)),
+			_ => if let Some(k) = param.strip_prefix("bijective") {
+				match k.parse::<u32>() {
+					Ok(k) if (1..=35).contains(&k) => Ok(Radix::Bijective(k, false)),
+					_ => Err(format!("Bijective bases go from 1 to 35, not {}", k))
+				}
+			} else {
+				match param.strip_prefix("perm").map(|n| n.parse::<usize>()) {
+					Some(Ok(n)) if n > MAX_PERMUTATION => Err(format!("Permutations have at most {} elements, not {}", MAX_PERMUTATION, n)),
+					Some(Ok(n)) if n > 0 => Ok(Radix::Permutation(n)),
+					_ => Err(format!("Unknown number system: {}", param))
+				}
+			}
+		}
+	}
+
+	fn name(&self) -> &str {
+		match self {
+			Radix::Negabinary => "negabinary",
+			Radix::Negadecimal => "negadecimal",
+			Radix::BalancedTernary(_) => "balanced ternary",
+			Radix::Factoradic => "factoradic",
+			Radix::Permutation(_) => "permutations",
+			Radix::Zeckendorf => "Zeckendorf",
+			Radix::Bijective(_, true) => "spreadsheet columns",
+			Radix::Bijective(_, false) => "bijective numeration"
+		}
+	}
+
+	/// The base of a positional number system, which is negative for negabinary and negadecimal
+	fn base(&self) -> i32 {
+		match self {
+			Radix::Negabinary => -2,
+			Radix::Negadecimal => -10,
+			Radix::BalancedTernary(_) => 3,
+			Radix::Factoradic | Radix::Permutation(_) | Radix::Zeckendorf | Radix::Bijective(..) => panic!()
+		}
+	}
+
+	fn digit_value(&self, c: char) -> Option<i32> {
+		match self {
+			Radix::BalancedTernary(_) => match c {
+				'T' | 't' | '-' => Some(-1),
+				'0' => Some(0),
+				'1' | '+' => Some(1),
+				_ => None
+			},
+			_ => c.to_digit(-self.base() as u32).map(|d| d as i32)
+		}
+	}
+
+	fn digit_char(&self, digit: i32) -> char {
+		match (self, digit) {
+			(Radix::BalancedTernary(true), -1) => '-',
+			(Radix::BalancedTernary(true), 1) => '+',
+			(Radix::BalancedTernary(false), -1) => 'T',
+			_ => char::from_digit(digit as u32, 10).unwrap()
+		}
+	}
+}
+
+fn small(n: u64) -> Decimal {
+	Decimal::from_digits(false, &n.to_string())
+}
+
+/// Adds a small signed integer to the decimal
+fn add_small(value: &Decimal, n: i32) -> Decimal {
+	value.add(&Decimal::from_digits(n < 0, &n.unsigned_abs().to_string()))
+}
+
+/// Reads the digits in arg as a number in the number system
+pub fn read_radix(arg: &str, radix: Radix) -> Result<Decimal, String> {
+	if arg.is_empty() {
+		return Err("Missing digits".to_string());
+	}
+	match radix {
+		Radix::Factoradic => read_factoradic(&read_digit_list(arg, radix)?),
+		Radix::Permutation(n) => read_permutation(&read_digit_list(arg, radix)?, n),
+		Radix::Zeckendorf => read_zeckendorf(arg),
+		Radix::Bijective(k, letters) => read_bijective(arg, k, letters),
+		Radix::Negabinary | Radix::Negadecimal | Radix::BalancedTernary(_) => read_positional(arg, radix)
+	}
+}
+
+/// Writes the integer value in the number system
+pub fn write_radix(value: &Decimal, radix: Radix) -> String {
+	match radix {
+		Radix::Factoradic => write_digit_list(&factoradic_digits(value)),
+		Radix::Permutation(n) => write_digit_list(&permutation(value, n)),
+		Radix::Zeckendorf => write_zeckendorf(value),
+		Radix::Bijective(k, letters) => write_bijective(value, k, letters),
+		Radix::Negabinary | Radix::Negadecimal | Radix::BalancedTernary(_) => write_positional(value, radix)
+	}
+}
+
+/// Checks that the integer value can be written in the number system
+pub fn check_range(value: &Decimal, radix: Radix) -> Result<(), String> {
+	match radix {
+		Radix::Factoradic | Radix::Permutation(_) | Radix::Zeckendorf | Radix::Bijective(..) if value.negative => {
+			Err(format!("Negative numbers can not be written in {}", radix.name()))
+		}
+		Radix::Bijective(..) if value.is_zero() => {
+			Err(format!("Zero has no digits in {}", radix.name()))
+		}
+		Radix::Permutation(n) if factoradic_digits(value).len() > n => {
+			Err(format!("{} is too large to index a permutation of {} elements", value, n))
+		}
+		_ => Ok(())
+	}
+}
+
+fn read_positional(arg: &str, radix: Radix) -> Result<Decimal, String> {
+	let base = radix.base();
+	let mut value = small(0);
+	for c in arg.chars() {
+		let digit = match radix.digit_value(c) {
+			Some(digit) => digit,
+			None => return Err(format!("Character {} not allowed in {}", c, radix.name()))
+		};
+		value = value.mul_small(base.unsigned_abs());
+		if base < 0 {
+			value = value.negate();
+		}
+		value = add_small(&value, digit);
+	}
+	Ok(value)
+}
+
+fn write_positional(value: &Decimal, radix: Radix) -> String {
+	let base = radix.base();
+	let mut value = value.clone();
+	let mut digits = Vec::new();
+	while !value.is_zero() {
+		// the digit that makes the rest of the value divisible by the base
+		let (_, rem) = value.div_small(base.unsigned_abs());
+		let mut digit = if value.negative { (base.unsigned_abs() - rem) % base.unsigned_abs() } else { rem } as i32;
+		if let Radix::BalancedTernary(_) = radix {
+			if digit == 2 {
+				digit = -1;
+			}
+		}
+		digits.push(radix.digit_char(digit));
+
+		let (quotient, _) = add_small(&value, -digit).div_small(base.unsigned_abs());
+		value = if base < 0 { quotient.negate() } else { quotient };
+	}
+	if digits.is_empty() {
+		digits.push('0');
+	}
+	digits.iter().rev().collect()
+}
+
+/// Reads digits written one character each (0-9 then A-Z), or as numbers separated by ':' such as "3:4:1:0:1:0"
+fn read_digit_list(arg: &str, radix: Radix) -> Result<Vec<u64>, String> {
+	if arg.contains(':') {
+		arg.split(':').map(|digit| digit.parse::<u64>().map_err(|_| format!("Invalid digit {} in {}", digit, radix.name()))).collect()
+	} else {
+		arg.chars().map(|c| c.to_digit(36).map(|d| d as u64).ok_or(format!("Character {} not allowed in {}", c, radix.name()))).collect()
+	}
+}
+
+/// Writes digits one character each if they are all below 10, otherwise separated by ':'
+fn write_digit_list(digits: &[u64]) -> String {
+	let strs: Vec<String> = digits.iter().map(|d| d.to_string()).collect();
+	if digits.iter().all(|d| *d < 10) { strs.concat() } else { strs.join(":") }
+}
+
+/// Reads factoradic digits, most significant first
+fn read_factoradic(digits: &[u64]) -> Result<Decimal, String> {
+	let mut value = small(0);
+	for (index, digit) in digits.iter().enumerate() {
+		let position = (digits.len() - 1 - index) as u64;
+		if *digit > position {
+			return Err(format!("Factoradic digit {} is too large for position {}", digit, position));
+		}
+		// multiply by the position above this one, so each digit ends up multiplied by its position's factorial
+		value = value.mul_small(position as u32 + 1).add(&small(*digit));
+	}
+	Ok(value)
+}
+
+/// Gets the factoradic digits of a non-negative integer, most significant first
+fn factoradic_digits(value: &Decimal) -> Vec<u64> {
+	// position 0 is always 0, and the remainders of dividing by 2, 3, ... fill the positions above it
+	let mut value = value.clone();
+	let mut digits = vec![0];
+	let mut position = 1;
+	while !value.is_zero() {
+		position += 1;
+		let (quotient, rem) = value.div_small(position);
+		digits.push(rem as u64);
+		value = quotient;
+	}
+	digits.reverse();
+	digits
+}
+
+/// Gets the permutation of 0..n whose Lehmer code is the factoradic digits of value
+fn permutation(value: &Decimal, n: usize) -> Vec<u64> {
+	let mut code = factoradic_digits(value);
+	while code.len() < n {
+		code.insert(0, 0);
+	}
+	let mut remaining: Vec<u64> = (0..n as u64).collect();
+	code.iter().map(|digit| remaining.remove(*digit as usize)).collect()
+}
+
+/// Reads a permutation of 0..n as the number its Lehmer code is the factoradic digits of
+fn read_permutation(elements: &[u64], n: usize) -> Result<Decimal, String> {
+	if elements.len() != n {
+		return Err(format!("Expected a permutation of {} elements but found {}", n, elements.len()));
+	}
+	let mut remaining: Vec<u64> = (0..n as u64).collect();
+	let mut code = Vec::new();
+	for element in elements {
+		match remaining.iter().position(|e| e == element) {
+			Some(index) => {
+				code.push(index as u64);
+				remaining.remove(index);
+			}
+			None => return Err(format!("{} is repeated or out of range in a permutation of {} elements", element, n))
+		}
+	}
+	read_factoradic(&code)
+}
+
+/// Gets the Fibonacci numbers 1, 2, 3, 5, ... up to and including the first one above value
+fn fibonacci_up_to(value: &Decimal) -> Vec<Decimal> {
+	let mut fibs = vec![small(1), small(2)];
+	while fibs.last().unwrap().cmp_magnitude(value).is_le() {
+		let next = fibs[fibs.len() - 1].add(&fibs[fibs.len() - 2]);
+		fibs.push(next);
+	}
+	fibs
+}
+
+fn read_zeckendorf(arg: &str) -> Result<Decimal, String> {
+	if arg.contains("11") {
+		return Err("Zeckendorf representations never have two 1s next to each other".to_string());
+	}
+	let mut fibs = vec![small(1), small(2)];
+	while fibs.len() < arg.len() {
+		let next = fibs[fibs.len() - 1].add(&fibs[fibs.len() - 2]);
+		fibs.push(next);
+	}
+	let mut value = small(0);
+	for (c, fib) in arg.chars().rev().zip(fibs.iter()) {
+		match c {
+			'0' => { }
+			'1' => value = value.add(fib),
+			_ => return Err(format!("Character {} not allowed in Zeckendorf", c))
+		}
+	}
+	Ok(value)
+}
+
+fn write_zeckendorf(value: &Decimal) -> String {
+	// greedily take the largest Fibonacci number that fits, which never takes two neighbours
+	let fibs = fibonacci_up_to(value);
+	let mut rest = value.clone();
+	let mut ret = String::new();
+	for fib in fibs[..fibs.len() - 1].iter().rev() {
+		if fib.cmp_magnitude(&rest).is_le() {
+			rest = rest.sub(fib);
+			ret.push('1');
+		} else if !ret.is_empty() {
+			ret.push('0');
+		}
+	}
+	if ret.is_empty() {
+		ret.push('0');
+	}
+	ret
+}
+
+/// Gets the value of a bijective digit, where letters count from A as 1 and other digits from 1 then A as 10
+fn bijective_digit_value(c: char, letters: bool) -> Option<u32> {
+	if letters {
+		c.is_ascii_alphabetic().then(|| c.to_ascii_uppercase() as u32 - 'A' as u32 + 1)
+	} else {
+		c.to_digit(36)
+	}
+}
+
+fn bijective_digit_char(digit: u32, letters: bool) -> char {
+	if letters {
+		char::from(b'A' + digit as u8 - 1)
+	} else {
+		char::from_digit(digit, 36).unwrap().to_ascii_uppercase()
+	}
+}
+
+fn read_bijective(arg: &str, k: u32, letters: bool) -> Result<Decimal, String> {
+	let mut value = small(0);
+	for c in arg.chars() {
+		match bijective_digit_value(c, letters) {
+			Some(digit) if (1..=k).contains(&digit) => value = value.mul_small(k).add(&small(digit as u64)),
+			_ => return Err(format!("Character {} not allowed in bijective base {}", c, k))
+		}
+	}
+	Ok(value)
+}
+
+fn write_bijective(value: &Decimal, k: u32, letters: bool) -> String {
+	let mut value = value.clone();
+	let mut digits = Vec::new();
+	while !value.is_zero() {
+		// a remainder of 0 is written as the digit k, borrowing one from the rest
+		let (quotient, rem) = value.div_small(k);
+		if rem == 0 {
+			digits.push(bijective_digit_char(k, letters));
+			value = quotient.sub(&small(1));
+		} else {
+			digits.push(bijective_digit_char(rem, letters));
+			value = quotient;
+		}
+	}
+	digits.iter().rev().collect()
+}
+
+#[cfg(test)]
+mod tests {
+	use crate::radix::*;
+
+	fn read_num(arg: &str, radix: Radix) -> String {
+		read_radix(arg, radix).unwrap().to_string()
+	}
+
+	fn write_num(num: i64, radix: Radix) -> String {
+		write_radix(&Decimal::parse(&num.to_string()).unwrap(), radix)
+	}
+
+	#[test]
+	fn negabase_tests() {
+		assert_eq!(write_num(2, Radix::Negabinary), "110");
+		assert_eq!(write_num(-1, Radix::Negabinary), "11");
+		assert_eq!(write_num(-2, Radix::Negabinary), "10");
+		assert_eq!(write_num(0, Radix::Negabinary), "0");
+		assert_eq!(write_num(-15, Radix::Negadecimal), "25");
+		assert_eq!(write_num(15, Radix::Negadecimal), "195");
+		assert_eq!(read_num("110", Radix::Negabinary), "2");
+		assert_eq!(read_num("25", Radix::Negadecimal), "-15");
+		for num in -100..100 {
+			assert_eq!(read_num(&write_num(num, Radix::Negabinary), Radix::Negabinary), num.to_string());
+			assert_eq!(read_num(&write_num(num, Radix::Negadecimal), Radix::Negadecimal), num.to_string());
+		}
+		assert!(read_radix("12", Radix::Negabinary).is_err());
+	}
+
+	#[test]
+	fn balanced_ternary_tests() {
+		assert_eq!(write_num(2, Radix::BalancedTernary(false)), "1T");
+		assert_eq!(write_num(-2, Radix::BalancedTernary(false)), "T1");
+		assert_eq!(write_num(8, Radix::BalancedTernary(true)), "+0-");
+		assert_eq!(read_num("1T0", Radix::BalancedTernary(false)), "6");
+		assert_eq!(read_num("-0+", Radix::BalancedTernary(false)), "-8");
+		for num in -100..100 {
+			assert_eq!(read_num(&write_num(num, Radix::BalancedTernary(false)), Radix::BalancedTernary(true)), num.to_string());
+		}
+		assert!(read_radix("12", Radix::BalancedTernary(false)).is_err());
+	}
+
+	#[test]
+	fn factoradic_tests() {
+		assert_eq!(write_num(463, Radix::Factoradic), "341010");
+		assert_eq!(write_num(0, Radix::Factoradic), "0");
+		assert_eq!(write_num(1, Radix::Factoradic), "10");
+		assert_eq!(write_num(36288000, Radix::Factoradic), "10:0:0:0:0:0:0:0:0:0:0");
+		assert_eq!(read_num("341010", Radix::Factoradic), "463");
+		assert_eq!(read_num("10:0:0:0:0:0:0:0:0:0:0", Radix::Factoradic), "36288000");
+		for num in 0..200 {
+			assert_eq!(read_num(&write_num(num, Radix::Factoradic), Radix::Factoradic), num.to_string());
+		}
+		assert!(read_radix("21", Radix::Factoradic).is_err());
+	}
+
+	#[test]
+	fn permutation_tests() {
+		assert_eq!(write_num(463, Radix::Permutation(6)), "351042");
+		assert_eq!(write_num(0, Radix::Permutation(4)), "0123");
+		assert_eq!(write_num(23, Radix::Permutation(4)), "3210");
+		assert_eq!(read_num("351042", Radix::Permutation(6)), "463");
+		assert_eq!(read_num("3210", Radix::Permutation(4)), "23");
+		assert!(check_range(&Decimal::parse("24").unwrap(), Radix::Permutation(4)).is_err());
+		assert!(read_radix("0012", Radix::Permutation(4)).is_err());
+		assert!(read_radix("012", Radix::Permutation(4)).is_err());
+		assert_eq!(Radix::parse("perm1000"), Ok(Radix::Permutation(1000)));
+		assert_eq!(Radix::parse("perm1000000"), Err("Permutations have at most 1000 elements, not 1000000".to_string()));
+		assert!(Radix::parse("perm0").is_err());
+	}
+
+	#[test]
+	fn zeckendorf_tests() {
+		assert_eq!(write_num(0, Radix::Zeckendorf), "0");
+		assert_eq!(write_num(1, Radix::Zeckendorf), "1");
+		assert_eq!(write_num(4, Radix::Zeckendorf), "101");
+		assert_eq!(write_num(100, Radix::Zeckendorf), "1000010100");
+		assert_eq!(read_num("1000010100", Radix::Zeckendorf), "100");
+		for num in 0..200 {
+			assert_eq!(read_num(&write_num(num, Radix::Zeckendorf), Radix::Zeckendorf), num.to_string());
+		}
+		assert!(read_radix("110", Radix::Zeckendorf).is_err());
+	}
+
+	#[test]
+	fn bijective_tests() {
+		let columns = Radix::parse("columns").unwrap();
+		assert_eq!(write_num(1, columns), "A");
+		assert_eq!(write_num(26, columns), "Z");
+		assert_eq!(write_num(27, columns), "AA");
+		assert_eq!(write_num(702, columns), "ZZ");
+		assert_eq!(write_num(16384, columns), "XFD");
+		assert_eq!(read_num("xfd", columns), "16384");
+		assert_eq!(write_num(10, Radix::Bijective(10, false)), "A");
+		assert_eq!(write_num(110, Radix::Bijective(10, false)), "AA");
+		assert_eq!(write_num(5, Radix::Bijective(1, false)), "11111");
+		for num in 1..1000 {
+			assert_eq!(read_num(&write_num(num, columns), columns), num.to_string());
+			assert_eq!(read_num(&write_num(num, Radix::Bijective(2, false)), Radix::Bijective(2, false)), num.to_string());
+		}
+		assert!(check_range(&Decimal::parse("0").unwrap(), columns).is_err());
+		assert!(read_radix("A0", columns).is_err());
+		assert!(read_radix("10", Radix::Bijective(10, false)).is_err());
+		assert!(Radix::parse("bijective36").is_err());
+	}
+}
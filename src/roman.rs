@@ -0,0 +1,190 @@
+/// How Roman numerals above 3999 are written
+#[derive(PartialEq, Eq, Debug)]
+#[derive(Clone, Copy)]
+pub enum RomanStyle {
+	/// only MMMCMXCIX and below
+	Standard,
+	/// a line over a numeral multiplies it by 1000, e.g. 5000 is V̅
+	Vinculum,
+	/// thousands written with reversed Cs, e.g. 5000 is IↃↃ and 10000 is CCIↃↃ
+	Apostrophus
+}
+
+impl RomanStyle {
+	pub fn parse(param: &str) -> Result<RomanStyle, String> {
+		match param {
+			"standard" => Ok(RomanStyle::Standard),
+			"vinculum" => Ok(RomanStyle::Vinculum),
+			"apostrophus" => Ok(RomanStyle::Apostrophus),
+			_ => Err(format!("Unknown Roman numeral style: {}", param))
+		}
+	}
+
+	fn max(&self) -> u64 {
+		match self {
+			RomanStyle::Standard => 3999,
+			RomanStyle::Vinculum => 3999999,
+			RomanStyle::Apostrophus => 399999
+		}
+	}
+}
+
+const OVERLINE: char = '\u{305}';
+const NUMERALS: [(u64, &str); 13] = [(1000, "M"), (900, "CM"), (500, "D"), (400, "CD"), (100, "C"), (90, "XC"), (50, "L"), (40, "XL"), (10, "X"), (9, "IX"), (5, "V"), (4, "IV"), (1, "I")];
+const SYMBOLS: [(u64, &str); 7] = [(1000, "M"), (500, "D"), (100, "C"), (50, "L"), (10, "X"), (5, "V"), (1, "I")];
+/// longest first, so reading can take the first one that matches
+const APOSTROPHUS: [(u64, &str); 5] = [(100000, "CCCIↃↃↃ"), (50000, "IↃↃↃ"), (10000, "CCIↃↃ"), (5000, "IↃↃ"), (1000, "CIↃ")];
+
+/// Checks that the integer value can be written as a Roman numeral
+pub fn check_range(value: &str, style: RomanStyle) -> Result<(), String> {
+	match value.parse::<u64>() {
+		Ok(0) => Err("Roman numerals have no zero".to_string()),
+		Ok(num) if num <= style.max() => Ok(()),
+		_ if value.starts_with('-') => Err("Roman numerals have no negative numbers".to_string()),
+		_ => Err(format!("{} is too large for Roman numerals, the largest is {}", value, style.max()))
+	}
+}
+
+fn write_standard(mut num: u64) -> String {
+	let mut ret = String::new();
+	for (value, numeral) in NUMERALS {
+		while num >= value {
+			ret.push_str(numeral);
+			num -= value;
+		}
+	}
+	ret
+}
+
+/// Writes a number from 1 up to the style's maximum as a Roman numeral
+pub fn write_roman(num: u64, style: RomanStyle) -> String {
+	if num <= 3999 {
+		return write_standard(num);
+	}
+	match style {
+		RomanStyle::Standard => panic!(),
+		RomanStyle::Vinculum => {
+			let thousands: String = write_standard(num / 1000).chars().flat_map(|c| [c, OVERLINE]).collect();
+			thousands + &write_standard(num % 1000)
+		}
+		RomanStyle::Apostrophus => {
+			// apostrophus thousands are only ever added, never subtracted
+			let mut thousands = num / 1000 * 1000;
+			let mut ret = String::new();
+			for (value, numeral) in APOSTROPHUS {
+				while thousands >= value {
+					ret.push_str(numeral);
+					thousands -= value;
+				}
+			}
+			ret + &write_standard(num % 1000)
+		}
+	}
+}
+
+/// Splits a Roman numeral into the values of its symbols
+fn symbol_values(arg: &str) -> Result<Vec<u64>, String> {
+	let mut values = Vec::new();
+	let mut rest = arg;
+	while let Some(c) = rest.chars().next() {
+		if let Some((value, numeral)) = APOSTROPHUS.iter().find(|(_, numeral)| rest.starts_with(numeral)) {
+			values.push(*value);
+			rest = &rest[numeral.len()..];
+			continue;
+		}
+		let value = match SYMBOLS.iter().find(|(_, symbol)| symbol.starts_with(c)) {
+			Some((value, _)) => *value,
+			None => return Err(format!("Character {} not allowed in Roman numerals", c))
+		};
+		rest = &rest[c.len_utf8()..];
+		match rest.strip_prefix(OVERLINE) {
+			Some(tmp_rest) => {
+				values.push(value * 1000);
+				rest = tmp_rest;
+			}
+			None => values.push(value)
+		}
+	}
+	if values.is_empty() {
+		return Err("Missing Roman numerals".to_string());
+	}
+	Ok(values)
+}
+
+/// Reads a Roman numeral, in any case and with ')' allowed for 'Ↄ'
+/// Unless lenient, the numeral has to be written the way write_roman would write it, so IIII and IC are rejected
+pub fn read_roman(arg: &str, lenient: bool) -> Result<u64, String> {
+	let normalised: String = arg.chars().map(|c| match c {
+		')' | 'ↄ' => 'Ↄ',
+		_ => c.to_ascii_uppercase()
+	}).collect();
+	let values = symbol_values(&normalised)?;
+
+	// a symbol followed by a larger one is subtracted
+	let mut num = 0i64;
+	for (index, value) in values.iter().enumerate() {
+		match values.get(index + 1) {
+			Some(next) if next > value => num -= *value as i64,
+			_ => num += *value as i64
+		}
+	}
+	if num <= 0 {
+		return Err(format!("{} is not a valid Roman numeral", arg));
+	}
+	let num = num as u64;
+
+	if !lenient {
+		let style = if normalised.contains(OVERLINE) {
+			RomanStyle::Vinculum
+		} else if normalised.contains('Ↄ') {
+			RomanStyle::Apostrophus
+		} else {
+			RomanStyle::Standard
+		};
+		if num > style.max() || write_roman(num, style) != normalised {
+			return Err(format!("{} is not a canonical Roman numeral", arg));
+		}
+	}
+	Ok(num)
+}
+
+#[cfg(test)]
+mod tests {
+	use crate::roman::*;
+
+	#[test]
+	fn write_roman_tests() {
+		assert_eq!(write_roman(1, RomanStyle::Standard), "I");
+		assert_eq!(write_roman(1994, RomanStyle::Standard), "MCMXCIV");
+		assert_eq!(write_roman(3999, RomanStyle::Vinculum), "MMMCMXCIX");
+		assert_eq!(write_roman(5000, RomanStyle::Vinculum), "V\u{305}");
+		assert_eq!(write_roman(14042, RomanStyle::Vinculum), "X\u{305}I\u{305}V\u{305}XLII");
+		assert_eq!(write_roman(16000, RomanStyle::Apostrophus), "CCIↃↃIↃↃCIↃ");
+		assert!(check_range("4000", RomanStyle::Standard).is_err());
+		assert!(check_range("0", RomanStyle::Vinculum).is_err());
+		assert!(check_range("-5", RomanStyle::Vinculum).is_err());
+		assert!(check_range("3999999", RomanStyle::Vinculum).is_ok());
+	}
+
+	#[test]
+	fn read_roman_tests() {
+		assert_eq!(read_roman("MCMXCIV", false), Ok(1994));
+		assert_eq!(read_roman("xlii", false), Ok(42));
+		assert_eq!(read_roman("V\u{305}", false), Ok(5000));
+		assert_eq!(read_roman("CCI))IↃↃCIↃ", false), Ok(16000));
+		assert!(read_roman("IIII", false).is_err());
+		assert!(read_roman("IC", false).is_err());
+		assert!(read_roman("MMMM", false).is_err());
+		assert_eq!(read_roman("IIII", true), Ok(4));
+		assert_eq!(read_roman("IC", true), Ok(99));
+		assert!(read_roman("IZ", true).is_err());
+		assert!(read_roman("", true).is_err());
+		for num in 1..4000 {
+			assert_eq!(read_roman(&write_roman(num, RomanStyle::Standard), false), Ok(num));
+		}
+		for num in (4000..400000).step_by(997) {
+			assert_eq!(read_roman(&write_roman(num, RomanStyle::Vinculum), false), Ok(num));
+			assert_eq!(read_roman(&write_roman(num, RomanStyle::Apostrophus), false), Ok(num));
+		}
+	}
+}
@@ -0,0 +1,60 @@
+use bitvec::prelude::BitVec;
+
+use crate::{write, WriteMode, WriteSeparator};
+
+/// Builds a report of bit statistics for the bit pattern in bits
+/// Leading and trailing zeroes are counted over the whole width of bits, so pad bits to the fixed width first
+pub fn report(bits: &BitVec) -> String {
+	let width = bits.len();
+	let popcount = bits.count_ones();
+	let highest = bits.first_one().map(|index| width - 1 - index);
+	let lowest = bits.last_one().map(|index| width - 1 - index);
+	let clz = bits.leading_zeros();
+	let ctz = if popcount == 0 { width } else { bits.trailing_zeros() };
+	let is_pow2 = popcount == 1;
+
+	// smallest power of two that is not less than the value
+	let mut next_pow2 = BitVec::new();
+	next_pow2.push(true);
+	if let Some(highest) = highest {
+		let shift = if is_pow2 { highest } else { highest + 1 };
+		for _ in 0..shift {
+			next_pow2.push(false);
+		}
+	}
+	while next_pow2.len() % 4 != 0 {
+		next_pow2.insert(0, false);
+	}
+
+	let index_str = |index: Option<usize>| match index {
+		Some(index) => index.to_string(),
+		None => "none".to_string()
+	};
+
+	format!("width={} popcount={} clz={} ctz={} highest={} lowest={} parity={} pow2={} log2={} next_pow2={}",
+		width,
+		popcount,
+		clz,
+		ctz,
+		index_str(highest),
+		index_str(lowest),
+		if popcount % 2 == 0 { "even" } else { "odd" },
+		is_pow2,
+		index_str(highest),
+		write(&mut next_pow2, WriteMode::Hex(true), &WriteSeparator::None, false, true))
+}
+
+#[cfg(test)]
+mod tests {
+	use bitvec::prelude::*;
+
+	use crate::stats::*;
+
+	#[test]
+	fn report_tests() {
+		assert_eq!(report(&bitvec![0, 0, 0, 1, 0, 0, 0, 0]), "width=8 popcount=1 clz=3 ctz=4 highest=4 lowest=4 parity=odd pow2=true log2=4 next_pow2=0x10");
+		assert_eq!(report(&bitvec![0, 1, 1, 0]), "width=4 popcount=2 clz=1 ctz=1 highest=2 lowest=1 parity=even pow2=false log2=2 next_pow2=0x8");
+		assert_eq!(report(&bitvec![0, 0, 0, 0]), "width=4 popcount=0 clz=4 ctz=4 highest=none lowest=none parity=even pow2=false log2=none next_pow2=0x1");
+		assert_eq!(report(&BitVec::new()), "width=0 popcount=0 clz=0 ctz=0 highest=none lowest=none parity=even pow2=false log2=none next_pow2=0x1");
+	}
+}
@@ -0,0 +1,332 @@
+use std::{iter::Peekable, str::Chars};
+
+use crate::unicode::{category, check_scalar};
+
+/// How characters are stored in bytes
+#[derive(PartialEq, Eq, Debug)]
+#[derive(Clone, Copy)]
+pub enum Encoding {
+	Ascii,
+	Latin1,
+	Utf8,
+	/// EBCDIC code page 37, which holds the same characters as Latin-1 in a different order
+	Cp037
+}
+
+/// Which language's escapes are written for characters that are not printable
+#[derive(PartialEq, Eq, Debug)]
+#[derive(Clone, Copy)]
+pub enum Escapes {
+	C,
+	Rust,
+	Python
+}
+
+#[derive(PartialEq, Eq, Debug)]
+#[derive(Clone, Copy)]
+pub struct TextFormat {
+	pub encoding: Encoding,
+	pub escapes: Escapes
+}
+
+impl Encoding {
+	pub fn parse(param: &str) -> Result<Encoding, String> {
+		match param.to_ascii_lowercase().as_str() {
+			"ascii" => Ok(Encoding::Ascii),
+			"latin1" | "latin-1" | "iso-8859-1" => Ok(Encoding::Latin1),
+			"utf8" | "utf-8" => Ok(Encoding::Utf8),
+			"cp037" | "ebcdic" => Ok(Encoding::Cp037),
+			_ => Err(format!("Unknown text encoding: {}", param))
+		}
+	}
+
+	fn name(&self) -> &str {
+		match self {
+			Encoding::Ascii => "ASCII",
+			Encoding::Latin1 => "Latin-1",
+			Encoding::Utf8 => "UTF-8",
+			Encoding::Cp037 => "CP037"
+		}
+	}
+}
+
+impl TextFormat {
+	pub fn parse(param: &str) -> Result<TextFormat, String> {
+		let (encoding, escapes) = param.split_once(':').unwrap_or((param, "c"));
+		let escapes = match escapes {
+			"c" => Escapes::C,
+			"rust" => Escapes::Rust,
+			"python" => Escapes::Python,
+			_ => return Err(format!("Unknown escapes {} for text {}, which are c, rust or python", escapes, param))
+		};
+		Ok(TextFormat { encoding: Encoding::parse(encoding)?, escapes })
+	}
+}
+
+/// The Latin-1 character of every CP037 byte
+const CP037: [u8; 256] = [
+	0x00, 0x01, 0x02, 0x03, 0x9C, 0x09, 0x86, 0x7F, 0x97, 0x8D, 0x8E, 0x0B, 0x0C, 0x0D, 0x0E, 0x0F,
+	0x10, 0x11, 0x12, 0x13, 0x9D, 0x85, 0x08, 0x87, 0x18, 0x19, 0x92, 0x8F, 0x1C, 0x1D, 0x1E, 0x1F,
+	0x80, 0x81, 0x82, 0x83, 0x84, 0x0A, 0x17, 0x1B, 0x88, 0x89, 0x8A, 0x8B, 0x8C, 0x05, 0x06, 0x07,
+	0x90, 0x91, 0x16, 0x93, 0x94, 0x95, 0x96, 0x04, 0x98, 0x99, 0x9A, 0x9B, 0x14, 0x15, 0x9E, 0x1A,
+	0x20, 0xA0, 0xE2, 0xE4, 0xE0, 0xE1, 0xE3, 0xE5, 0xE7, 0xF1, 0xA2, 0x2E, 0x3C, 0x28, 0x2B, 0x7C,
+	0x26, 0xE9, 0xEA, 0xEB, 0xE8, 0xED, 0xEE, 0xEF, 0xEC, 0xDF, 0x21, 0x24, 0x2A, 0x29, 0x3B, 0xAC,
+	0x2D, 0x2F, 0xC2, 0xC4, 0xC0, 0xC1, 0xC3, 0xC5, 0xC7, 0xD1, 0xA6, 0x2C, 0x25, 0x5F, 0x3E, 0x3F,
+	0xF8, 0xC9, 0xCA, 0xCB, 0xC8, 0xCD, 0xCE, 0xCF, 0xCC, 0x60, 0x3A, 0x23, 0x40, 0x27, 0x3D, 0x22,
+	0xD8, 0x61, 0x62, 0x63, 0x64, 0x65, 0x66, 0x67, 0x68, 0x69, 0xAB, 0xBB, 0xF0, 0xFD, 0xFE, 0xB1,
+	0xB0, 0x6A, 0x6B, 0x6C, 0x6D, 0x6E, 0x6F, 0x70, 0x71, 0x72, 0xAA, 0xBA, 0xE6, 0xB8, 0xC6, 0xA4,
+	0xB5, 0x7E, 0x73, 0x74, 0x75, 0x76, 0x77, 0x78, 0x79, 0x7A, 0xA1, 0xBF, 0xD0, 0xDD, 0xDE, 0xAE,
+	0x5E, 0xA3, 0xA5, 0xB7, 0xA9, 0xA7, 0xB6, 0xBC, 0xBD, 0xBE, 0x5B, 0x5D, 0xAF, 0xA8, 0xB4, 0xD7,
+	0x7B, 0x41, 0x42, 0x43, 0x44, 0x45, 0x46, 0x47, 0x48, 0x49, 0xAD, 0xF4, 0xF6, 0xF2, 0xF3, 0xF5,
+	0x7D, 0x4A, 0x4B, 0x4C, 0x4D, 0x4E, 0x4F, 0x50, 0x51, 0x52, 0xB9, 0xFB, 0xFC, 0xF9, 0xFA, 0xFF,
+	0x5C, 0xF7, 0x53, 0x54, 0x55, 0x56, 0x57, 0x58, 0x59, 0x5A, 0xB2, 0xD4, 0xD6, 0xD2, 0xD3, 0xD5,
+	0x30, 0x31, 0x32, 0x33, 0x34, 0x35, 0x36, 0x37, 0x38, 0x39, 0xB3, 0xDB, 0xDC, 0xD9, 0xDA, 0x9F
+];
+
+/// A piece of a string literal: a character, or a byte given by a numeric escape such as \x7F or \177
+#[derive(PartialEq, Eq, Debug)]
+pub enum Piece {
+	Char(char),
+	Byte(u8)
+}
+
+/// Takes up to max digits of the radix from chars, after the first digit if there is one
+/// Returns the value of the digits and how many there were
+fn digits(chars: &mut Peekable<Chars>, first: Option<char>, radix: u32, max: usize) -> (u32, usize) {
+	let (mut value, mut len) = match first {
+		Some(first) => (first.to_digit(radix).unwrap(), 1),
+		None => (0, 0)
+	};
+	while len < max {
+		match chars.peek().and_then(|c| c.to_digit(radix)) {
+			Some(digit) => value = value * radix + digit,
+			None => break
+		}
+		chars.next();
+		len += 1;
+	}
+	(value, len)
+}
+
+/// Splits a string into characters and bytes, reading the escapes of C, Rust and Python
+/// \x takes at most two digits and octal escapes at most three, so '\x41BC' is 'ABC'
+pub fn parse_escapes(arg: &str) -> Result<Vec<Piece>, String> {
+	let mut pieces = Vec::new();
+	let mut chars = arg.chars().peekable();
+	while let Some(c) = chars.next() {
+		if c != '\\' {
+			pieces.push(Piece::Char(c));
+			continue;
+		}
+		let escape = chars.next().ok_or(format!("{} ends in the middle of an escape", arg))?;
+		let piece = match escape {
+			'a' => Piece::Char('\x07'),
+			'b' => Piece::Char('\x08'),
+			'e' => Piece::Char('\x1B'),
+			'f' => Piece::Char('\x0C'),
+			'n' => Piece::Char('\n'),
+			'r' => Piece::Char('\r'),
+			't' => Piece::Char('\t'),
+			'v' => Piece::Char('\x0B'),
+			'\\' | '\'' | '"' | '?' => Piece::Char(escape),
+			'0'..='7' => match digits(&mut chars, Some(escape), 8, 3) {
+				(value, _) if value <= 0xFF => Piece::Byte(value as u8),
+				_ => return Err(format!("Octal escape in {} is more than a byte", arg))
+			},
+			'x' => match digits(&mut chars, None, 16, 2) {
+				(_, 0) => return Err(format!("\\x in {} has no hexadecimal digits", arg)),
+				(value, _) => Piece::Byte(value as u8)
+			},
+			'u' | 'U' => {
+				let code_point = if escape == 'u' && chars.peek() == Some(&'{') {
+					chars.next();
+					let (value, len) = digits(&mut chars, None, 16, 6);
+					if len == 0 || chars.next() != Some('}') {
+						return Err(format!("\\u{{ in {} needs 1 to 6 hexadecimal digits and a }}", arg));
+					}
+					value
+				} else {
+					let len = if escape == 'u' { 4 } else { 8 };
+					match digits(&mut chars, None, 16, len) {
+						(value, read) if read == len => value,
+						_ => return Err(format!("\\{} in {} needs exactly {} hexadecimal digits", escape, arg, len))
+					}
+				};
+				Piece::Char(check_scalar(code_point)?)
+			}
+			_ => return Err(format!("Unknown escape \\{} in {}", escape, arg))
+		};
+		pieces.push(piece);
+	}
+	Ok(pieces)
+}
+
+/// Encodes a character, or returns an Err if the encoding has no byte for it
+fn encode_char(c: char, encoding: Encoding, bytes: &mut Vec<u8>) -> Result<(), String> {
+	let code_point = c as u32;
+	match encoding {
+		Encoding::Ascii if code_point < 0x80 => bytes.push(code_point as u8),
+		Encoding::Latin1 if code_point < 0x100 => bytes.push(code_point as u8),
+		Encoding::Utf8 => bytes.extend(c.to_string().as_bytes()),
+		Encoding::Cp037 if code_point < 0x100 => bytes.push(CP037.iter().position(|latin1| *latin1 as u32 == code_point).unwrap() as u8),
+		_ => return Err(format!("{} has no character U+{:04X}; write its bytes with \\x", encoding.name(), code_point))
+	}
+	Ok(())
+}
+
+/// Reads a string with escapes into the bytes of the encoding, where numeric byte escapes are written as they are
+pub fn read_text(arg: &str, encoding: Encoding) -> Result<Vec<u8>, String> {
+	let mut bytes = Vec::new();
+	for piece in parse_escapes(arg)? {
+		match piece {
+			Piece::Char(c) => encode_char(c, encoding, &mut bytes)?,
+			Piece::Byte(byte) => bytes.push(byte)
+		}
+	}
+	Ok(bytes)
+}
+
+/// Reads a character literal such as 'A', '\n', b'\x7F' or the multi-character constant 'RIFF'
+/// Returns the value of a single character, or the bytes of a multi-character constant with its characters in UTF-8
+pub fn read_char_literal(arg: &str) -> Result<Vec<u32>, String> {
+	let (is_byte, quoted) = match arg.strip_prefix('b') {
+		Some(quoted) => (true, quoted),
+		None => (false, arg)
+	};
+	let inner = quoted.strip_prefix('\'').and_then(|rest| rest.strip_suffix('\'')).filter(|inner| !inner.is_empty())
+		.ok_or(format!("{} is not a character literal, which is written in single quotes", arg))?;
+	let pieces = parse_escapes(inner)?;
+	if is_byte && !matches!(pieces[..], [Piece::Byte(_)] | [Piece::Char('\0'..='\x7F')]) {
+		return Err(format!("{} is not a byte literal, which is one ASCII character or escape", arg));
+	}
+	if let [Piece::Char(c)] = pieces[..] {
+		return Ok(vec![c as u32]);
+	}
+	// C packs the source bytes of a multi-character constant into the value, the first being the most significant
+	let mut bytes = Vec::new();
+	for piece in pieces {
+		match piece {
+			Piece::Char(c) => encode_char(c, Encoding::Utf8, &mut bytes)?,
+			Piece::Byte(byte) => bytes.push(byte)
+		}
+	}
+	Ok(bytes.iter().map(|byte| *byte as u32).collect())
+}
+
+/// Writes the bytes as a character literal with C escapes, such as 'A' or the multi-character constant 'RIFF'
+pub fn write_char_literal(bytes: &[u8]) -> String {
+	format!("'{}'", write_text(bytes, TextFormat { encoding: Encoding::Ascii, escapes: Escapes::C }).replace('\'', "\\'"))
+}
+
+/// Decodes the bytes into characters, keeping the bytes that are not characters of the encoding
+fn decode(bytes: &[u8], encoding: Encoding) -> Vec<Piece> {
+	match encoding {
+		Encoding::Ascii => bytes.iter().map(|b| if *b < 0x80 { Piece::Char(*b as char) } else { Piece::Byte(*b) }).collect(),
+		Encoding::Latin1 => bytes.iter().map(|b| Piece::Char(*b as char)).collect(),
+		Encoding::Cp037 => bytes.iter().map(|b| Piece::Char(CP037[*b as usize] as char)).collect(),
+		Encoding::Utf8 => bytes.utf8_chunks().flat_map(|chunk| {
+			chunk.valid().chars().map(Piece::Char).chain(chunk.invalid().iter().map(|b| Piece::Byte(*b))).collect::<Vec<_>>()
+		}).collect()
+	}
+}
+
+/// Writes a byte with a numeric escape, in octal for C so that a following hexadecimal digit is not read as part of it
+fn escape_byte(byte: u8, escapes: Escapes) -> String {
+	match escapes {
+		Escapes::C => format!("\\{:03o}", byte),
+		Escapes::Rust | Escapes::Python => format!("\\x{:02X}", byte)
+	}
+}
+
+/// Writes the bytes as a string in the encoding, with escapes for the characters that are not printable
+/// Characters of single byte encodings are escaped as their byte, so the string reads back to the same bytes
+pub fn write_text(bytes: &[u8], format: TextFormat) -> String {
+	let single_byte = format.encoding != Encoding::Utf8;
+	let mut ret = String::new();
+	for piece in decode(bytes, format.encoding) {
+		let c = match piece {
+			Piece::Char(c) => c,
+			Piece::Byte(byte) => {
+				ret.push_str(&escape_byte(byte, format.escapes));
+				continue;
+			}
+		};
+		let named = match (c, format.escapes) {
+			('\\', _) => Some("\\\\"),
+			('\n', _) => Some("\\n"),
+			('\r', _) => Some("\\r"),
+			('\t', _) => Some("\\t"),
+			('\x07', Escapes::C | Escapes::Python) => Some("\\a"),
+			('\x08', Escapes::C | Escapes::Python) => Some("\\b"),
+			('\x0B', Escapes::C | Escapes::Python) => Some("\\v"),
+			('\x0C', Escapes::C | Escapes::Python) => Some("\\f"),
+			_ => None
+		};
+		if let Some(named) = named {
+			ret.push_str(named);
+		} else if !matches!(category(c as u32), "Cc" | "Cf" | "Co" | "Cn" | "Zl" | "Zp") {
+			ret.push(c);
+		} else if single_byte || (c as u32) < 0x80 {
+			let mut char_bytes = Vec::new();
+			encode_char(c, format.encoding, &mut char_bytes).unwrap();
+			ret.push_str(&escape_byte(char_bytes[0], format.escapes));
+		} else {
+			ret.push_str(&match (format.escapes, c as u32) {
+				(Escapes::Rust, code_point) => format!("\\u{{{:X}}}", code_point),
+				(Escapes::C | Escapes::Python, code_point @ ..=0xFFFF) => format!("\\u{:04X}", code_point),
+				(Escapes::C | Escapes::Python, code_point) => format!("\\U{:08X}", code_point)
+			});
+		}
+	}
+	ret
+}
+
+#[cfg(test)]
+mod tests {
+	use crate::text::*;
+
+	fn format(encoding: Encoding, escapes: Escapes) -> TextFormat {
+		TextFormat { encoding, escapes }
+	}
+
+	#[test]
+	fn read_tests() {
+		assert_eq!(read_text("Hello\\n", Encoding::Ascii), Ok(b"Hello\n".to_vec()));
+		assert_eq!(read_text("\\x41BC\\101\\0", Encoding::Ascii), Ok(b"ABCA\0".to_vec()));
+		assert_eq!(read_text("é\\u{1F600}", Encoding::Utf8), Ok(vec![0xC3, 0xA9, 0xF0, 0x9F, 0x98, 0x80]));
+		assert_eq!(read_text("\\u00E9\\xFF", Encoding::Latin1), Ok(vec![0xE9, 0xFF]));
+		assert_eq!(read_text("A1\\n", Encoding::Cp037), Ok(vec![0xC1, 0xF1, 0x25]));
+		assert!(read_text("é", Encoding::Ascii).is_err());
+		assert!(read_text("\\q", Encoding::Ascii).is_err());
+		assert!(read_text("\\u12", Encoding::Utf8).is_err());
+		assert!(read_text("\\", Encoding::Utf8).is_err());
+	}
+
+	#[test]
+	fn char_literal_tests() {
+		assert_eq!(read_char_literal("'A'"), Ok(vec![0x41]));
+		assert_eq!(read_char_literal("'\\n'"), Ok(vec![0x0A]));
+		assert_eq!(read_char_literal("b'\\x7f'"), Ok(vec![0x7F]));
+		assert_eq!(read_char_literal("'é'"), Ok(vec![0xE9]));
+		assert_eq!(read_char_literal("'RIFF'"), Ok(vec![0x52, 0x49, 0x46, 0x46]));
+		assert!(read_char_literal("''").is_err());
+		assert!(read_char_literal("'A").is_err());
+		assert!(read_char_literal("b'AB'").is_err());
+		assert!(read_char_literal("b'é'").is_err());
+		assert_eq!(read_char_literal("'aé'"), Ok(vec![0x61, 0xC3, 0xA9]));
+		assert_eq!(read_char_literal("'\\u00E9\\x01'"), Ok(vec![0xC3, 0xA9, 0x01]));
+		assert_eq!(write_char_literal(b"mp4a"), "'mp4a'");
+		assert_eq!(write_char_literal(&[0x27, 0x0A, 0x00]), "'\\'\\n\\000'");
+	}
+
+	#[test]
+	fn write_tests() {
+		assert_eq!(write_text(b"Hello\n", format(Encoding::Ascii, Escapes::C)), "Hello\\n");
+		assert_eq!(write_text(&[0x00, 0x41, 0x7F, 0xFF], format(Encoding::Ascii, Escapes::C)), "\\000A\\177\\377");
+		assert_eq!(write_text(&[0x00, 0x41, 0x7F, 0xFF], format(Encoding::Ascii, Escapes::Rust)), "\\x00A\\x7F\\xFF");
+		assert_eq!(write_text(&[0x07, 0x5C, 0xE9], format(Encoding::Latin1, Escapes::Python)), "\\a\\\\é");
+		assert_eq!(write_text(&[0xC3, 0xA9, 0xE2, 0x80, 0x8B, 0xFF], format(Encoding::Utf8, Escapes::Rust)), "é\\u{200B}\\xFF");
+		assert_eq!(write_text(&[0xF3, 0xB0, 0x80, 0x80], format(Encoding::Utf8, Escapes::Python)), "\\U000F0000");
+		assert_eq!(write_text(&[0xC1, 0xF1, 0x25, 0x04], format(Encoding::Cp037, Escapes::C)), "A1\\n\\004");
+	}
+}
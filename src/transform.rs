@@ -0,0 +1,515 @@
+use bitvec::prelude::BitVec;
+
+use crate::{read, ReadMode, WriteLength, WriteMode};
+
+/// Inclusive range of bit indices written verilog style as [hi:lo], where bit 0 is the least significant bit
+#[derive(PartialEq, Eq, Debug)]
+#[derive(Clone, Copy)]
+pub struct BitRange {
+	pub hi: usize,
+	pub lo: usize
+}
+
+/// An operation applied to the number between reading and writing it
+#[derive(PartialEq, Eq, Debug)]
+pub enum Transform {
+	Set(Vec<BitRange>),
+	Clear(Vec<BitRange>),
+	Toggle(Vec<BitRange>),
+	Extract(BitRange),
+	Insert(BitRange, BitVec),
+	ByteSwap,
+	WordSwap(usize /* half word width in bits */),
+	Pdp,
+	Reverse,
+	RotateLeft(usize),
+	RotateRight(usize),
+	NibbleSwap,
+	Pdep(BitVec /* mask */),
+	Pext(BitVec /* mask */),
+	SignExtend(usize /* from width */, usize /* to width */),
+	ZeroExtend(usize /* from width */, usize /* to width */),
+	Truncate(usize /* to width */),
+	GrayEncode,
+	GrayDecode
+}
+
+impl BitRange {
+	/// Parses a range in the form "hi:lo" or a single bit index "n"
+	pub fn parse(param: &str) -> Result<BitRange, String> {
+		let parse_index = |s: &str| s.trim().parse::<usize>().map_err(|_| format!("Invalid bit index: {}", s));
+		let range = match param.split_once(':') {
+			Some((hi, lo)) => BitRange { hi: parse_index(hi)?, lo: parse_index(lo)? },
+			None => {
+				let index = parse_index(param)?;
+				BitRange { hi: index, lo: index }
+			}
+		};
+		if range.hi < range.lo {
+			return Err(format!("Bit range [{}:{}] must be written high to low", range.hi, range.lo));
+		}
+		Ok(range)
+	}
+
+	/// Parses a comma separated list of ranges, e.g. "3,4,15:8"
+	pub fn parse_list(param: &str) -> Result<Vec<BitRange>, String> {
+		param.split(',').map(BitRange::parse).collect()
+	}
+
+	/// Number of bits covered by the range
+	pub fn width(&self) -> usize {
+		self.hi - self.lo + 1
+	}
+}
+
+impl Transform {
+	/// Gets if the operation reorders the bytes of a byte sequence
+	pub fn is_byte_order(&self) -> bool {
+		matches!(self, Transform::ByteSwap | Transform::WordSwap(_) | Transform::Pdp)
+	}
+
+	/// Gets if the operation casts the integer to a new width
+	pub fn is_cast(&self) -> bool {
+		matches!(self, Transform::SignExtend(..) | Transform::ZeroExtend(..) | Transform::Truncate(_))
+	}
+
+	/// Parses the parameter of --insert in the form "hi:lo=value"
+	pub fn parse_insert(param: &str) -> Result<Transform, String> {
+		let (range, value) = match param.split_once('=') {
+			Some(split) => split,
+			None => return Err(format!("Expected <hi:lo>=<value> but found {}", param))
+		};
+		let range = BitRange::parse(range)?;
+		let bits = read(&value.to_string(), ReadMode::Interpret, WriteMode::Binary, WriteLength::Unfixed, false)?;
+		if bits.len() > range.width() {
+			return Err(format!("Value {} does not fit in bits [{}:{}]", value, range.hi, range.lo));
+		}
+		Ok(Transform::Insert(range, bits))
+	}
+
+	/// Parses the amount to rotate by
+	pub fn parse_rotate(param: &str, left: bool) -> Result<Transform, String> {
+		match param.parse::<usize>() {
+			Ok(n) if left => Ok(Transform::RotateLeft(n)),
+			Ok(n) => Ok(Transform::RotateRight(n)),
+			Err(_) => Err(format!("Invalid rotate amount: {}", param))
+		}
+	}
+
+	/// Parses the "from:to" widths of --sext and --zext
+	pub fn parse_extend(param: &str, signed: bool) -> Result<Transform, String> {
+		let parse_width = |s: &str| match s.trim().parse::<usize>() {
+			Ok(0) | Err(_) => Err(format!("Invalid width: {}", s)),
+			Ok(width) => Ok(width)
+		};
+		let (from, to) = match param.split_once(':') {
+			Some((from, to)) => (parse_width(from)?, parse_width(to)?),
+			None => return Err(format!("Expected <from>:<to> but found {}", param))
+		};
+		if to < from {
+			return Err(format!("Cannot extend from {} bits to the narrower {} bits", from, to));
+		}
+		if signed {
+			Ok(Transform::SignExtend(from, to))
+		} else {
+			Ok(Transform::ZeroExtend(from, to))
+		}
+	}
+
+	/// Parses the width of --trunc
+	pub fn parse_truncate(param: &str) -> Result<Transform, String> {
+		match param.trim().parse::<usize>() {
+			Ok(width) if width > 0 => Ok(Transform::Truncate(width)),
+			_ => Err(format!("Invalid width: {}", param))
+		}
+	}
+
+	/// Parses the mask of --pdep and --pext
+	pub fn parse_mask(param: &str, deposit: bool) -> Result<Transform, String> {
+		let mask = read(&param.to_string(), ReadMode::Interpret, WriteMode::Binary, WriteLength::Unfixed, false)?;
+		if deposit {
+			Ok(Transform::Pdep(mask))
+		} else {
+			Ok(Transform::Pext(mask))
+		}
+	}
+}
+
+/// Widens bits to width by repeating the sign bit (signed_mode) or adding leading zeroes
+pub fn extend(bits: &mut BitVec, width: usize, signed_mode: bool) {
+	let fill = signed_mode && bits.first().is_some_and(|b| *b);
+	while bits.len() < width {
+		bits.insert(0, fill);
+	}
+}
+
+/// Pads unfixed widths to a whole number of bytes (at least one), so the result does not depend on how the number was written
+fn whole_bytes(bits: &mut BitVec, fixed_width: Option<usize>, signed_mode: bool) {
+	if fixed_width.is_none() {
+		extend(bits, bits.len().next_multiple_of(8).max(8), signed_mode);
+	}
+}
+
+/// Makes sure that the bit at index exists, growing bits if the width is not fixed
+fn reserve_bit(bits: &mut BitVec, index: usize, fixed_width: Option<usize>, signed_mode: bool) -> Result<(), String> {
+	if index < bits.len() {
+		return Ok(());
+	}
+	match fixed_width {
+		Some(width) => Err(format!("Bit {} is outside of the {} bit fixed width", index, width)),
+		None => {
+			extend(bits, index + 1, signed_mode);
+			Ok(())
+		}
+	}
+}
+
+/// Applies f to the value of every bit in each range, storing the result
+fn map_ranges(bits: &mut BitVec, ranges: &[BitRange], fixed_width: Option<usize>, signed_mode: bool, f: fn(bool) -> bool) -> Result<(), String> {
+	for range in ranges {
+		reserve_bit(bits, range.hi, fixed_width, signed_mode)?;
+		let len = bits.len();
+		for index in range.lo..=range.hi {
+			let bit = bits[len - 1 - index];
+			bits.set(len - 1 - index, f(bit));
+		}
+	}
+	Ok(())
+}
+
+/// Reverses the order of the unit sized chunks of bits within every block sized chunk
+/// Unfixed widths are padded to a whole number of blocks first
+fn reorder_units(bits: &mut BitVec, unit: usize, block: Option<usize>, fixed_width: Option<usize>, signed_mode: bool) -> Result<(), String> {
+	let block_len = block.unwrap_or(unit);
+	match fixed_width {
+		Some(width) if width % block_len != 0 => {
+			return Err(format!("Cannot reorder {} bit units of a {} bit fixed width", unit, width));
+		}
+		Some(_) => { }
+		None => {
+			let width = bits.len().next_multiple_of(block_len);
+			extend(bits, width, signed_mode);
+		}
+	}
+
+	let block_len = block.unwrap_or(bits.len());
+	if block_len == 0 {
+		return Ok(());
+	}
+	let mut reordered = BitVec::with_capacity(bits.len());
+	for block in bits.chunks(block_len) {
+		for unit in block.chunks(unit).rev() {
+			reordered.extend_from_bitslice(unit);
+		}
+	}
+	*bits = reordered;
+	Ok(())
+}
+
+/// Makes bits at least as wide as mask, failing if the mask does not fit in the fixed width
+fn fit_mask(bits: &mut BitVec, mask: &BitVec, fixed_width: Option<usize>, signed_mode: bool) -> Result<(), String> {
+	let mask_len = mask.len() - mask.leading_zeros();
+	if mask_len > 0 {
+		reserve_bit(bits, mask_len - 1, fixed_width, signed_mode)?;
+	}
+	Ok(())
+}
+
+/// Casts the integer to a new width, keeping the low from bits and extending them to width to
+/// signed selects extending with the sign bit of the from bit field rather than with zeroes
+/// In fixed widths, the result is then zero-extended up to the fixed width
+fn cast(bits: &mut BitVec, from: usize, to: usize, signed: bool, fixed_width: Option<usize>, signed_mode: bool) -> Result<(), String> {
+	if let Some(width) = fixed_width {
+		if to > width {
+			return Err(format!("Cannot cast to {} bits in a {} bit fixed width", to, width));
+		}
+	}
+
+	// take the source field
+	extend(bits, from, signed_mode);
+	let mut field = bits[bits.len() - from..].to_bitvec();
+
+	// widen or narrow to the destination
+	if from > to {
+		field = field[from - to..].to_bitvec();
+	}
+	extend(&mut field, to, signed);
+	if let Some(width) = fixed_width {
+		extend(&mut field, width, false);
+	}
+	*bits = field;
+	Ok(())
+}
+
+/// Gets the bit at index (counting from the least significant bit), treating missing bits as 0
+fn bit_at(bits: &BitVec, index: usize) -> bool {
+	index < bits.len() && bits[bits.len() - 1 - index]
+}
+
+/// Applies the transform to the integer in bits
+/// fixed_width is the width in bits that the integer must keep, or None if the width may change
+pub fn apply(bits: &mut BitVec, transform: &Transform, fixed_width: Option<usize>, signed_mode: bool) -> Result<(), String> {
+	if let Some(width) = fixed_width {
+		extend(bits, width, signed_mode);
+	}
+
+	match transform {
+		Transform::Set(ranges) => map_ranges(bits, ranges, fixed_width, signed_mode, |_| true),
+		Transform::Clear(ranges) => map_ranges(bits, ranges, fixed_width, signed_mode, |_| false),
+		Transform::Toggle(ranges) => map_ranges(bits, ranges, fixed_width, signed_mode, |b| !b),
+		Transform::Extract(range) => {
+			reserve_bit(bits, range.hi, fixed_width, signed_mode)?;
+			let len = bits.len();
+			let mut field = bits[len - 1 - range.hi..len - range.lo].to_bitvec();
+			if let Some(width) = fixed_width {
+				extend(&mut field, width, false);
+			}
+			*bits = field;
+			Ok(())
+		}
+		Transform::Insert(range, value) => {
+			reserve_bit(bits, range.hi, fixed_width, signed_mode)?;
+			let mut field = value.clone();
+			extend(&mut field, range.width(), false);
+			let len = bits.len();
+			bits[len - 1 - range.hi..len - range.lo].copy_from_bitslice(&field);
+			Ok(())
+		}
+		Transform::ByteSwap => reorder_units(bits, 8, None, fixed_width, signed_mode),
+		Transform::WordSwap(half) => reorder_units(bits, *half, Some(half * 2), fixed_width, signed_mode),
+		Transform::Pdp => reorder_units(bits, 8, Some(16), fixed_width, signed_mode),
+		Transform::Reverse => {
+			whole_bytes(bits, fixed_width, signed_mode);
+			bits.reverse();
+			Ok(())
+		}
+		Transform::RotateLeft(n) => {
+			whole_bytes(bits, fixed_width, signed_mode);
+			// a zero width has nothing to rotate
+			let n = n.checked_rem(bits.len()).unwrap_or(0);
+			bits.rotate_left(n);
+			Ok(())
+		}
+		Transform::RotateRight(n) => {
+			whole_bytes(bits, fixed_width, signed_mode);
+			// a zero width has nothing to rotate
+			let n = n.checked_rem(bits.len()).unwrap_or(0);
+			bits.rotate_right(n);
+			Ok(())
+		}
+		Transform::NibbleSwap => reorder_units(bits, 4, Some(8), fixed_width, signed_mode),
+		Transform::Pdep(mask) => {
+			fit_mask(bits, mask, fixed_width, signed_mode)?;
+			let len = bits.len();
+			let mut deposited = BitVec::repeat(false, len);
+			let mut next = 0;
+			for index in 0..len {
+				if bit_at(mask, index) {
+					deposited.set(len - 1 - index, bit_at(bits, next));
+					next += 1;
+				}
+			}
+			*bits = deposited;
+			Ok(())
+		}
+		Transform::Pext(mask) => {
+			fit_mask(bits, mask, fixed_width, signed_mode)?;
+			let len = bits.len();
+			let mut extracted = BitVec::repeat(false, len);
+			let mut next = 0;
+			for index in 0..len {
+				if bit_at(mask, index) {
+					extracted.set(len - 1 - next, bit_at(bits, index));
+					next += 1;
+				}
+			}
+			*bits = extracted;
+			Ok(())
+		}
+		Transform::SignExtend(from, to) => cast(bits, *from, *to, true, fixed_width, signed_mode),
+		Transform::ZeroExtend(from, to) => cast(bits, *from, *to, false, fixed_width, signed_mode),
+		Transform::Truncate(to) => {
+			let from = bits.len().max(*to);
+			cast(bits, from, *to, signed_mode, fixed_width, signed_mode)
+		}
+		Transform::GrayEncode => {
+			// every bit becomes itself xor the bit above it
+			for index in (1..bits.len()).rev() {
+				let above = bits[index - 1];
+				let bit = bits[index];
+				bits.set(index, bit ^ above);
+			}
+			Ok(())
+		}
+		Transform::GrayDecode => {
+			// every bit becomes itself xor all the bits above it
+			for index in 1..bits.len() {
+				let above = bits[index - 1];
+				let bit = bits[index];
+				bits.set(index, bit ^ above);
+			}
+			Ok(())
+		}
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use crate::bytes::bits_of;
+	use crate::transform::*;
+
+	#[test]
+	fn parse_range_tests() {
+		assert_eq!(BitRange::parse("7"), Ok(BitRange { hi: 7, lo: 7 }));
+		assert_eq!(BitRange::parse("15:8"), Ok(BitRange { hi: 15, lo: 8 }));
+		assert_eq!(BitRange::parse_list("3,4"), Ok(vec![BitRange { hi: 3, lo: 3 }, BitRange { hi: 4, lo: 4 }]));
+		assert!(BitRange::parse("8:15").is_err());
+		assert!(BitRange::parse("x").is_err());
+		assert!(Transform::parse_insert("3:0=0x1F").is_err());
+	}
+
+	#[test]
+	fn set_clear_toggle_tests() {
+		let mut bits = bits_of(0x10, 8);
+		apply(&mut bits, &Transform::Set(BitRange::parse_list("7").unwrap()), None, false).unwrap();
+		assert_eq!(bits, bits_of(0x90, 8));
+		apply(&mut bits, &Transform::Clear(BitRange::parse_list("4,7").unwrap()), None, false).unwrap();
+		assert_eq!(bits, bits_of(0x00, 8));
+		apply(&mut bits, &Transform::Toggle(BitRange::parse_list("3:0").unwrap()), None, false).unwrap();
+		assert_eq!(bits, bits_of(0x0F, 8));
+
+		// unfixed widths grow to fit the bit
+		apply(&mut bits, &Transform::Set(BitRange::parse_list("11").unwrap()), None, false).unwrap();
+		assert_eq!(bits, bits_of(0x80F, 12));
+
+		// fixed widths do not
+		let mut bits = bits_of(0, 8);
+		assert!(apply(&mut bits, &Transform::Set(BitRange::parse_list("8").unwrap()), Some(8), false).is_err());
+	}
+
+	#[test]
+	fn extract_insert_tests() {
+		let mut bits = bits_of(0xDEADBEEF, 32);
+		apply(&mut bits, &Transform::Extract(BitRange::parse("15:8").unwrap()), None, false).unwrap();
+		assert_eq!(bits, bits_of(0xBE, 8));
+
+		let mut bits = bits_of(0xDEADBEEF, 32);
+		apply(&mut bits, &Transform::Extract(BitRange::parse("15:8").unwrap()), Some(32), false).unwrap();
+		assert_eq!(bits, bits_of(0xBE, 32));
+
+		let mut bits = bits_of(0xFFFF, 16);
+		apply(&mut bits, &Transform::parse_insert("11:8=0x5").unwrap(), None, false).unwrap();
+		assert_eq!(bits, bits_of(0xF5FF, 16));
+	}
+
+	#[test]
+	fn byte_order_tests() {
+		let mut bits = bits_of(0x123456, 24);
+		apply(&mut bits, &Transform::ByteSwap, None, false).unwrap();
+		assert_eq!(bits, bits_of(0x563412, 24));
+
+		// unfixed widths are rounded up to whole bytes
+		let mut bits = bits_of(0x123, 12);
+		apply(&mut bits, &Transform::ByteSwap, None, false).unwrap();
+		assert_eq!(bits, bits_of(0x2301, 16));
+
+		let mut bits = bits_of(0x1122334455667788, 64);
+		apply(&mut bits, &Transform::WordSwap(16), None, false).unwrap();
+		assert_eq!(bits, bits_of(0x3344112277885566, 64));
+		apply(&mut bits, &Transform::WordSwap(32), None, false).unwrap();
+		assert_eq!(bits, bits_of(0x7788556633441122, 64));
+		apply(&mut bits, &Transform::Pdp, None, false).unwrap();
+		assert_eq!(bits, bits_of(0x8877665544332211, 64));
+
+		let mut bits = bits_of(0x1234, 16);
+		assert!(apply(&mut bits, &Transform::WordSwap(16), Some(16), false).is_err());
+	}
+
+	#[test]
+	fn permutation_tests() {
+		let mut bits = bits_of(0x01, 8);
+		apply(&mut bits, &Transform::Reverse, None, false).unwrap();
+		assert_eq!(bits, bits_of(0x80, 8));
+		apply(&mut bits, &Transform::RotateLeft(1), None, false).unwrap();
+		assert_eq!(bits, bits_of(0x01, 8));
+		apply(&mut bits, &Transform::RotateRight(10), None, false).unwrap();
+		assert_eq!(bits, bits_of(0x40, 8));
+		let mut bits = BitVec::new();
+		apply(&mut bits, &Transform::RotateLeft(1), Some(0), false).unwrap();
+		assert!(bits.is_empty());
+
+		// unfixed widths are whole bytes, however many digits the number was read with
+		let mut bits = bits_of(0b101, 3);
+		apply(&mut bits, &Transform::Reverse, None, false).unwrap();
+		assert_eq!(bits, bits_of(0xA0, 8));
+		let mut bits = bits_of(0x1, 4);
+		apply(&mut bits, &Transform::RotateRight(1), None, false).unwrap();
+		assert_eq!(bits, bits_of(0x80, 8));
+		let mut bits = bits_of(0x100, 9);
+		apply(&mut bits, &Transform::Reverse, None, false).unwrap();
+		assert_eq!(bits, bits_of(0x0080, 16));
+		let mut bits = bits_of(0x1, 4);
+		apply(&mut bits, &Transform::Reverse, Some(4), false).unwrap();
+		assert_eq!(bits, bits_of(0x8, 4));
+
+		let mut bits = bits_of(0x12AB, 16);
+		apply(&mut bits, &Transform::NibbleSwap, None, false).unwrap();
+		assert_eq!(bits, bits_of(0x21BA, 16));
+
+		let mut bits = bits_of(0b1011, 4);
+		apply(&mut bits, &Transform::GrayEncode, None, false).unwrap();
+		assert_eq!(bits, bits_of(0b1110, 4));
+		apply(&mut bits, &Transform::GrayDecode, None, false).unwrap();
+		assert_eq!(bits, bits_of(0b1011, 4));
+		for num in 0..255u128 {
+			let (mut current, mut next) = (bits_of(num, 8), bits_of(num + 1, 8));
+			apply(&mut current, &Transform::GrayEncode, None, false).unwrap();
+			apply(&mut next, &Transform::GrayEncode, None, false).unwrap();
+			assert_eq!(current.iter().zip(next.iter()).filter(|(a, b)| a != b).count(), 1);
+		}
+
+		let mut bits = bits_of(0x1234, 16);
+		apply(&mut bits, &Transform::parse_mask("0xF0F0", false).unwrap(), None, false).unwrap();
+		assert_eq!(bits, bits_of(0x0013, 16));
+		apply(&mut bits, &Transform::parse_mask("0xF0F0", true).unwrap(), None, false).unwrap();
+		assert_eq!(bits, bits_of(0x1030, 16));
+
+		// the mask is wider than the value
+		let mut bits = bits_of(0x3, 4);
+		apply(&mut bits, &Transform::parse_mask("0x101", true).unwrap(), None, false).unwrap();
+		assert_eq!(bits, bits_of(0x101, 9));
+		let mut bits = bits_of(0x3, 8);
+		assert!(apply(&mut bits, &Transform::parse_mask("0x101", true).unwrap(), Some(8), false).is_err());
+	}
+
+	#[test]
+	fn cast_tests() {
+		let mut bits = bits_of(0xF80, 12);
+		apply(&mut bits, &Transform::parse_extend("12:32", true).unwrap(), None, false).unwrap();
+		assert_eq!(bits, bits_of(0xFFFFFF80, 32));
+
+		// only the low bits of the source are considered
+		let mut bits = bits_of(0xABF80, 20);
+		apply(&mut bits, &Transform::parse_extend("12:16", true).unwrap(), None, false).unwrap();
+		assert_eq!(bits, bits_of(0xFF80, 16));
+		let mut bits = bits_of(0xABF80, 20);
+		apply(&mut bits, &Transform::parse_extend("8:64", false).unwrap(), None, false).unwrap();
+		assert_eq!(bits, bits_of(0x80, 64));
+
+		let mut bits = bits_of(0x12345678, 32);
+		apply(&mut bits, &Transform::parse_truncate("16").unwrap(), None, false).unwrap();
+		assert_eq!(bits, bits_of(0x5678, 16));
+
+		// in a fixed width the cast result is zero-extended to the width
+		let mut bits = bits_of(0x80, 32);
+		apply(&mut bits, &Transform::parse_extend("8:16", true).unwrap(), Some(32), false).unwrap();
+		assert_eq!(bits, bits_of(0x0000FF80, 32));
+		let mut bits = bits_of(0x1FF, 16);
+		apply(&mut bits, &Transform::parse_truncate("8").unwrap(), Some(16), true).unwrap();
+		assert_eq!(bits, bits_of(0x00FF, 16));
+
+		assert!(Transform::parse_extend("32:12", true).is_err());
+		let mut bits = bits_of(0x1, 16);
+		assert!(apply(&mut bits, &Transform::parse_extend("8:32", false).unwrap(), Some(16), false).is_err());
+	}
+}
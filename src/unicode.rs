@@ -0,0 +1,218 @@
+use crate::unicode_data::{CATEGORIES, NAMES, NUMBERED_NAMES};
+use crate::bytes::hex_bytes;
+
+/// The largest Unicode code point
+pub const MAX_CODE_POINT: u32 = 0x10FFFF;
+
+/// A Unicode encoding form, where UTF-16 and UTF-32 are big-endian unless marked little-endian
+#[derive(PartialEq, Eq, Debug)]
+#[derive(Clone, Copy)]
+pub enum Utf {
+	Utf8,
+	Utf16(bool /* is little-endian */),
+	Utf32(bool /* is little-endian */)
+}
+
+impl Utf {
+	pub fn parse(param: &str) -> Result<Utf, String> {
+		let lower = param.to_ascii_lowercase();
+		match lower.strip_prefix("utf").unwrap_or(&lower).trim_start_matches('-') {
+			"8" => Ok(Utf::Utf8),
+			"16" | "16be" => Ok(Utf::Utf16(false)),
+			"16le" => Ok(Utf::Utf16(true)),
+			"32" | "32be" => Ok(Utf::Utf32(false)),
+			"32le" => Ok(Utf::Utf32(true)),
+			_ => Err(format!("Unknown Unicode encoding: {}", param))
+		}
+	}
+
+	/// Gets the number of bytes written together as one group, which is a code unit when big-endian and a byte otherwise
+	pub fn group_len(&self) -> usize {
+		match self {
+			Utf::Utf16(false) => 2,
+			Utf::Utf32(false) => 4,
+			Utf::Utf8 | Utf::Utf16(true) | Utf::Utf32(true) => 1
+		}
+	}
+}
+
+fn parse_hex(digits: &str, arg: &str) -> Result<u32, String> {
+	if digits.is_empty() || digits.len() > 6 || !digits.chars().all(|c| c.is_ascii_hexdigit()) {
+		return Err(format!("{} is not a code point", arg));
+	}
+	let code_point = u32::from_str_radix(digits, 16).unwrap();
+	if code_point > MAX_CODE_POINT {
+		return Err(format!("{} is past the last code point U+10FFFF", arg));
+	}
+	Ok(code_point)
+}
+
+/// Reads a code point written as U+1F600, \u{1F600}, \uD83D\uDE00 (a UTF-16 surrogate pair) or the character itself
+pub fn read_code_point(arg: &str) -> Result<u32, String> {
+	if let Some(digits) = arg.strip_prefix("U+").or(arg.strip_prefix("u+")) {
+		return parse_hex(digits, arg);
+	}
+	if let Some(digits) = arg.strip_prefix("\\u{").and_then(|rest| rest.strip_suffix('}')) {
+		return parse_hex(digits, arg);
+	}
+	if let Some(rest) = arg.strip_prefix("\\u") {
+		let units: Vec<u16> = rest.split("\\u").map(|digits| match digits.len() {
+			4 => parse_hex(digits, arg).map(|unit| unit as u16),
+			_ => Err(format!("{} needs exactly 4 hexadecimal digits after each \\u", arg))
+		}).collect::<Result<_, _>>()?;
+		let mut chars = char::decode_utf16(units.iter().copied());
+		return match (chars.next(), chars.next()) {
+			(Some(Ok(c)), None) => Ok(c as u32),
+			(Some(Err(_)), _) => Err(format!("{} has an unpaired surrogate", arg)),
+			_ => Err(format!("{} is more than one character", arg))
+		};
+	}
+	let mut chars = arg.chars();
+	match (chars.next(), chars.next()) {
+		(Some(c), None) => Ok(c as u32),
+		(None, _) => Err("Missing character".to_string()),
+		_ => Err(format!("{} is more than one character; write code points as U+XXXX", arg))
+	}
+}
+
+/// Checks that the code point can be encoded, which is not true of the surrogates
+pub fn check_scalar(code_point: u32) -> Result<char, String> {
+	char::from_u32(code_point).ok_or(match code_point {
+		0xD800..=0xDFFF => format!("U+{:04X} is a surrogate, which cannot be encoded on its own", code_point),
+		_ => format!("0x{:X} is past the last code point U+10FFFF", code_point)
+	})
+}
+
+/// Encodes the code point into the bytes of the encoding
+pub fn encode(code_point: u32, utf: Utf) -> Result<Vec<u8>, String> {
+	let c = check_scalar(code_point)?;
+	Ok(match utf {
+		Utf::Utf8 => c.to_string().into_bytes(),
+		Utf::Utf16(le) => c.encode_utf16(&mut [0; 2]).iter().flat_map(|unit| if le { unit.to_le_bytes() } else { unit.to_be_bytes() }).collect(),
+		Utf::Utf32(le) => if le { code_point.to_le_bytes().to_vec() } else { code_point.to_be_bytes().to_vec() }
+	})
+}
+
+/// Decodes the bytes of a single character written in hexadecimal in arg
+pub fn decode(arg: &str, utf: Utf) -> Result<u32, String> {
+	let bytes = hex_bytes(arg)?;
+	let chars: Vec<char> = match utf {
+		Utf::Utf8 => std::str::from_utf8(&bytes).map_err(|_| format!("{} is not valid UTF-8", arg))?.chars().collect(),
+		Utf::Utf16(le) => {
+			if bytes.len() % 2 != 0 {
+				return Err(format!("{} is not a whole number of UTF-16 code units", arg));
+			}
+			let units = bytes.chunks(2).map(|unit| if le { u16::from_le_bytes([unit[0], unit[1]]) } else { u16::from_be_bytes([unit[0], unit[1]]) });
+			char::decode_utf16(units).collect::<Result<_, _>>().map_err(|_| format!("{} has an unpaired surrogate", arg))?
+		}
+		Utf::Utf32(le) => {
+			if bytes.len() % 4 != 0 {
+				return Err(format!("{} is not a whole number of UTF-32 code units", arg));
+			}
+			bytes.chunks(4).map(|unit| {
+				let unit = [unit[0], unit[1], unit[2], unit[3]];
+				check_scalar(if le { u32::from_le_bytes(unit) } else { u32::from_be_bytes(unit) })
+			}).collect::<Result<_, _>>()?
+		}
+	};
+	match chars[..] {
+		[c] => Ok(c as u32),
+		_ => Err(format!("{} is {} characters, not one", arg, chars.len()))
+	}
+}
+
+/// Gets the two letter general category of the code point, such as Lu or So
+pub fn category(code_point: u32) -> &'static str {
+	let index = CATEGORIES.partition_point(|(start, _)| *start <= code_point);
+	CATEGORIES[index - 1].1
+}
+
+/// Gets the name of the code point if it is in the embedded table or named by a rule
+pub fn name(code_point: u32) -> Option<String> {
+	if let Ok(index) = NAMES.binary_search_by_key(&code_point, |(c, _)| *c) {
+		return Some(NAMES[index].1.to_string());
+	}
+	if let Some((_, _, prefix)) = NUMBERED_NAMES.iter().find(|(start, end, _)| (*start..=*end).contains(&code_point)) {
+		return Some(format!("{}-{:04X}", prefix, code_point));
+	}
+	if (0xAC00..=0xD7A3).contains(&code_point) {
+		// Hangul syllables are named by their leading consonant, vowel and trailing consonant
+		const LEADS: [&str; 19] = ["G", "GG", "N", "D", "DD", "R", "M", "B", "BB", "S", "SS", "", "J", "JJ", "C", "K", "T", "P", "H"];
+		const VOWELS: [&str; 21] = ["A", "AE", "YA", "YAE", "EO", "E", "YEO", "YE", "O", "WA", "WAE", "OE", "YO", "U", "WEO", "WE", "WI", "YU", "EU", "YI", "I"];
+		const TRAILS: [&str; 28] = ["", "G", "GG", "GS", "N", "NJ", "NH", "D", "L", "LG", "LM", "LB", "LS", "LT", "LP", "LH", "M", "B", "BS", "S", "SS", "NG", "J", "C", "K", "T", "P", "H"];
+		let index = (code_point - 0xAC00) as usize;
+		return Some(format!("HANGUL SYLLABLE {}{}{}", LEADS[index / 588], VOWELS[index % 588 / 28], TRAILS[index % 28]));
+	}
+	if category(code_point) == "Cc" {
+		return Some("<control>".to_string());
+	}
+	None
+}
+
+/// Writes the code point in the U+XXXX form
+pub fn write_code_point(code_point: u32) -> String {
+	format!("U+{:04X}", code_point)
+}
+
+/// Describes the code point, e.g. 'U+1F600 name=GRINNING FACE category=So'
+/// Characters outside the blocks of the embedded name table are marked with name=<not in table>
+pub fn write_fields(code_point: u32) -> String {
+	let mut ret = write_code_point(code_point);
+	match name(code_point) {
+		Some(name) => ret.push_str(&format!(" name={}", name)),
+		// unassigned, surrogate and private use code points have no names
+		None if matches!(category(code_point), "Cn" | "Cs" | "Co") => {}
+		None => ret.push_str(" name=<not in table>")
+	}
+	ret.push_str(&format!(" category={}", category(code_point)));
+	ret
+}
+
+#[cfg(test)]
+mod tests {
+	use crate::unicode::*;
+
+	#[test]
+	fn code_point_tests() {
+		assert_eq!(read_code_point("U+1F600"), Ok(0x1F600));
+		assert_eq!(read_code_point("u+e9"), Ok(0xE9));
+		assert_eq!(read_code_point("\\u{1F600}"), Ok(0x1F600));
+		assert_eq!(read_code_point("\\uD83D\\uDE00"), Ok(0x1F600));
+		assert_eq!(read_code_point("\\u00E9"), Ok(0xE9));
+		assert_eq!(read_code_point("😀"), Ok(0x1F600));
+		assert_eq!(read_code_point(" "), Ok(0x20));
+		assert!(read_code_point("U+110000").is_err());
+		assert!(read_code_point("\\uD83D").is_err());
+		assert!(read_code_point("\\uE9").is_err());
+		assert!(read_code_point("ab").is_err());
+	}
+
+	#[test]
+	fn encoding_tests() {
+		assert_eq!(encode(0x1F600, Utf::Utf8), Ok(vec![0xF0, 0x9F, 0x98, 0x80]));
+		assert_eq!(encode(0x1F600, Utf::Utf16(false)), Ok(vec![0xD8, 0x3D, 0xDE, 0x00]));
+		assert_eq!(encode(0x1F600, Utf::Utf16(true)), Ok(vec![0x3D, 0xD8, 0x00, 0xDE]));
+		assert_eq!(encode(0xE9, Utf::Utf32(false)), Ok(vec![0, 0, 0, 0xE9]));
+		assert!(encode(0xD800, Utf::Utf8).is_err());
+		assert_eq!(decode("F09F9880", Utf::Utf8), Ok(0x1F600));
+		assert_eq!(decode("D83DDE00", Utf::Utf16(false)), Ok(0x1F600));
+		assert_eq!(decode("E9000000", Utf::Utf32(true)), Ok(0xE9));
+		assert!(decode("C0AF", Utf::Utf8).is_err());
+		assert!(decode("4142", Utf::Utf8).is_err());
+		assert!(decode("D83D", Utf::Utf16(false)).is_err());
+	}
+
+	#[test]
+	fn name_tests() {
+		assert_eq!(write_fields(0x1F600), "U+1F600 name=GRINNING FACE category=So");
+		assert_eq!(write_fields(0x41), "U+0041 name=LATIN CAPITAL LETTER A category=Lu");
+		assert_eq!(write_fields(0x0A), "U+000A name=<control> category=Cc");
+		assert_eq!(write_fields(0x4E2D), "U+4E2D name=CJK UNIFIED IDEOGRAPH-4E2D category=Lo");
+		assert_eq!(write_fields(0xD55C), "U+D55C name=HANGUL SYLLABLE HAN category=Lo");
+		assert_eq!(write_fields(0xD800), "U+D800 category=Cs");
+		assert_eq!(write_fields(0xE000), "U+E000 category=Co");
+		assert_eq!(write_fields(0x05D0), "U+05D0 name=<not in table> category=Lo");
+		assert_eq!(write_fields(0xFF21), "U+FF21 name=<not in table> category=Lu");
+		assert_eq!(write_fields(0x10FFFF), "U+10FFFF category=Cn");
+	}
+}
@@ -0,0 +1,293 @@
+use bitvec::prelude::BitVec;
+
+use crate::bytes::{bits_of, hex_bytes};
+
+/// A variable-length encoding of an integer in bytes
+#[derive(PartialEq, Eq, Debug)]
+#[derive(Clone, Copy)]
+pub enum Varint {
+	/// 7 bits per byte from the least significant, with the top bit set on all bytes but the last
+	Uleb128,
+	/// like unsigned LEB128 of the two's complement, ending once the rest is all sign bits
+	Sleb128,
+	/// unsigned LEB128 of a 64 bit integer, where negative numbers take 10 bytes,
+	/// or of the ZigZag encoding for sint fields (0, -1, 1, -2 are 0, 1, 2, 3)
+	Protobuf(bool /* ZigZag */),
+	/// a 62 bit integer in 1, 2, 4 or 8 big-endian bytes, with the length in the top 2 bits
+	Quic,
+	/// 7 bits per byte from the most significant, with the top bit set on all bytes but the last, as in MIDI
+	Vlq,
+	/// like VLQ, but each byte before the last stands for one more than its bits, as in Git's pack offsets
+	Git
+}
+
+impl Varint {
+	pub fn parse(param: &str) -> Result<Varint, String> {
+		match param {
+			"uleb128" | "leb128" => Ok(Varint::Uleb128),
+			"sleb128" => Ok(Varint::Sleb128),
+			"protobuf" | "varint" => Ok(Varint::Protobuf(false)),
+			"zigzag" | "sint" => Ok(Varint::Protobuf(true)),
+			"quic" => Ok(Varint::Quic),
+			"vlq" | "midi" => Ok(Varint::Vlq),
+			"git" => Ok(Varint::Git),
+			_ => Err(format!("Unknown variable-length encoding: {}", param))
+		}
+	}
+
+	fn name(&self) -> &str {
+		match self {
+			Varint::Uleb128 => "ULEB128",
+			Varint::Sleb128 => "SLEB128",
+			Varint::Protobuf(false) => "Protobuf varint",
+			Varint::Protobuf(true) => "Protobuf ZigZag varint",
+			Varint::Quic => "QUIC varint",
+			Varint::Vlq => "VLQ",
+			Varint::Git => "Git varint"
+		}
+	}
+}
+
+/// Gets the value of the bits (signed if signed_mode) if it fits in an i128
+pub fn to_i128(bits: &BitVec, signed_mode: bool) -> Option<i128> {
+	let negative = signed_mode && bits.first().is_some_and(|b| *b);
+	let significant = bits.iter().skip_while(|b| **b == negative).count();
+	if significant > 126 {
+		return None;
+	}
+	// starting from all sign bits, shift in the bits after the leading sign bits
+	Some(bits.iter().skip(bits.len() - significant).fold(if negative { -1i128 } else { 0 }, |n, b| (n << 1) | *b as i128))
+}
+
+/// Splits the bits (least significant first) into bytes of 7 bits from the least significant, with the top bit set on all but the last
+fn leb128(lsb_bits: &[bool]) -> Vec<u8> {
+	let mut bytes: Vec<u8> = lsb_bits.chunks(7).map(|group| group.iter().rev().fold(0, |n, b| (n << 1) | *b as u8) | 0x80).collect();
+	if bytes.is_empty() {
+		bytes.push(0);
+	}
+	*bytes.last_mut().unwrap() &= 0x7F;
+	bytes
+}
+
+/// Encodes the value of the bits (signed if signed_mode)
+pub fn encode(bits: &BitVec, signed_mode: bool, varint: Varint) -> Result<Vec<u8>, String> {
+	let negative = signed_mode && bits.first().is_some_and(|b| *b);
+	let out_of_range = || format!("Number too large for {}", varint.name());
+	match varint {
+		Varint::Uleb128 | Varint::Vlq if negative => {
+			Err(format!("Negative numbers can not be written in {}, use sleb128 or zigzag", varint.name()))
+		}
+		Varint::Uleb128 => {
+			let lsb_bits: Vec<bool> = bits.iter().rev().map(|b| *b).collect();
+			let len = lsb_bits.iter().rposition(|b| *b).map_or(0, |i| i + 1);
+			Ok(leb128(&lsb_bits[..len]))
+		}
+		Varint::Sleb128 => {
+			// the bits up to and including the last one that differs from the sign, then one sign bit
+			let lsb_bits: Vec<bool> = bits.iter().rev().map(|b| *b).collect();
+			let len = lsb_bits.iter().rposition(|b| *b != negative).map_or(0, |i| i + 1) + 1;
+			let mut extended = lsb_bits;
+			extended.resize(len.next_multiple_of(7), negative);
+			Ok(leb128(&extended))
+		}
+		Varint::Protobuf(zigzag) => {
+			let value = to_i128(bits, signed_mode).ok_or_else(out_of_range)?;
+			let encoded = if zigzag {
+				if value < i64::MIN as i128 || value > i64::MAX as i128 {
+					return Err(out_of_range());
+				}
+				((value << 1) ^ (value >> 63)) as u64
+			} else {
+				if value < i64::MIN as i128 || value > u64::MAX as i128 {
+					return Err(out_of_range());
+				}
+				value as u64
+			};
+			let lsb_bits: Vec<bool> = (0..64).map(|i| (encoded >> i) & 1 == 1).collect();
+			let len = lsb_bits.iter().rposition(|b| *b).map_or(0, |i| i + 1);
+			Ok(leb128(&lsb_bits[..len]))
+		}
+		Varint::Quic => {
+			let value = to_i128(bits, signed_mode).ok_or_else(out_of_range)?;
+			if value < 0 {
+				return Err("Negative numbers can not be written in QUIC varints".to_string());
+			}
+			let (len, prefix) = match value {
+				0..=0x3F => (1, 0x00),
+				0x40..=0x3FFF => (2, 0x40),
+				0x4000..=0x3FFF_FFFF => (4, 0x80),
+				0x4000_0000..=0x3FFF_FFFF_FFFF_FFFF => (8, 0xC0),
+				_ => return Err(out_of_range())
+			};
+			let mut bytes = (value as u64).to_be_bytes()[8 - len..].to_vec();
+			bytes[0] |= prefix;
+			Ok(bytes)
+		}
+		Varint::Vlq => {
+			let lsb_bits: Vec<bool> = bits.iter().rev().map(|b| *b).collect();
+			let len = lsb_bits.iter().rposition(|b| *b).map_or(0, |i| i + 1);
+			let mut bytes = leb128(&lsb_bits[..len]);
+			bytes.reverse();
+			let last = bytes.len() - 1;
+			for (index, byte) in bytes.iter_mut().enumerate() {
+				*byte = if index == last { *byte & 0x7F } else { *byte | 0x80 };
+			}
+			Ok(bytes)
+		}
+		Varint::Git => {
+			let value = to_i128(bits, signed_mode).ok_or_else(out_of_range)?;
+			if value < 0 {
+				return Err("Negative numbers can not be written in Git varints".to_string());
+			}
+			if value > u64::MAX as i128 {
+				return Err(out_of_range());
+			}
+			let mut value = value as u64;
+			let mut bytes = vec![(value & 0x7F) as u8];
+			value >>= 7;
+			while value != 0 {
+				value -= 1;
+				bytes.insert(0, 0x80 | (value & 0x7F) as u8);
+				value >>= 7;
+			}
+			Ok(bytes)
+		}
+	}
+}
+
+/// Gets the bytes up to the first without its top bit set, which must be the last
+fn continued_bytes(bytes: &[u8], varint: Varint) -> Result<&[u8], String> {
+	match bytes.iter().position(|b| b & 0x80 == 0) {
+		Some(last) if last + 1 == bytes.len() => Ok(bytes),
+		Some(_) => Err(format!("Bytes left over after the end of the {} number", varint.name())),
+		None => Err(format!("{} number ends in the middle", varint.name()))
+	}
+}
+
+/// Decodes the bytes written in hexadecimal in arg
+/// Returns whether the number is negative and the bits of its magnitude
+pub fn decode(arg: &str, varint: Varint) -> Result<(bool, BitVec), String> {
+	let bytes = hex_bytes(arg)?;
+	match varint {
+		Varint::Uleb128 | Varint::Sleb128 | Varint::Protobuf(_) => {
+			let bytes = continued_bytes(&bytes, varint)?;
+			let mut lsb_bits: Vec<bool> = bytes.iter().flat_map(|b| (0..7).map(move |i| (b >> i) & 1 == 1)).collect();
+			if let Varint::Protobuf(zigzag) = varint {
+				if lsb_bits[64.min(lsb_bits.len())..].iter().any(|b| *b) || bytes.len() > 10 {
+					return Err("Protobuf varints are at most 64 bits".to_string());
+				}
+				lsb_bits.resize(64, false);
+				let value = lsb_bits.iter().rev().fold(0u64, |n, b| (n << 1) | *b as u64);
+				if !zigzag {
+					// int64 fields are the same 64 bits, which read as negative with -s -w=8
+					return Ok((false, bits_of(value as u128, 128)));
+				}
+				let value = ((value >> 1) as i64) ^ -((value & 1) as i64);
+				return Ok((value < 0, bits_of(value.unsigned_abs() as u128, 128)));
+			}
+			let negative = varint == Varint::Sleb128 && *lsb_bits.last().unwrap();
+			let mut bits: BitVec = lsb_bits.iter().rev().collect();
+			if negative {
+				// two's complement back to the magnitude
+				for mut bit in bits.iter_mut() {
+					*bit = !*bit;
+				}
+				for index in (0..bits.len()).rev() {
+					let bit = bits[index];
+					bits.set(index, !bit);
+					if !bit {
+						break;
+					}
+				}
+			}
+			Ok((negative, bits))
+		}
+		Varint::Quic => {
+			let len = 1 << (bytes[0] >> 6);
+			if bytes.len() != len {
+				return Err(format!("Quic number has {} bytes but its prefix says {}", bytes.len(), len));
+			}
+			let value = bytes.iter().fold(0u128, |n, b| (n << 8) | *b as u128) & ((1 << (len * 8 - 2)) - 1);
+			Ok((false, bits_of(value, 128)))
+		}
+		Varint::Vlq | Varint::Git => {
+			let bytes = continued_bytes(&bytes, varint)?;
+			if varint == Varint::Vlq {
+				return Ok((false, bytes.iter().flat_map(|b| (0..7).rev().map(move |i| (b >> i) & 1 == 1)).collect()));
+			}
+			let mut value = 0u128;
+			for (index, byte) in bytes.iter().enumerate() {
+				if index > 0 {
+					value += 1;
+				}
+				value = (value << 7) | (byte & 0x7F) as u128;
+				if value > u64::MAX as u128 {
+					return Err("Git offsets are at most 64 bits".to_string());
+				}
+			}
+			Ok((false, bits_of(value, 128)))
+		}
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use crate::bytes::num_of;
+	use crate::varint::*;
+
+	fn value_of(decoded: (bool, BitVec)) -> i128 {
+		let magnitude = num_of(&decoded.1) as i128;
+		if decoded.0 { -magnitude } else { magnitude }
+	}
+
+	#[test]
+	fn leb128_tests() {
+		assert_eq!(encode(&bits_of(624485, 32), false, Varint::Uleb128), Ok(vec![0xE5, 0x8E, 0x26]));
+		assert_eq!(encode(&bits_of(0, 8), false, Varint::Uleb128), Ok(vec![0x00]));
+		assert_eq!(encode(&bits_of(-123456i128 as u128, 32), true, Varint::Sleb128), Ok(vec![0xC0, 0xBB, 0x78]));
+		assert_eq!(encode(&bits_of(63, 8), true, Varint::Sleb128), Ok(vec![0x3F]));
+		assert_eq!(encode(&bits_of(64, 8), false, Varint::Sleb128), Ok(vec![0xC0, 0x00]));
+		assert_eq!(encode(&bits_of(-64i128 as u128, 8), true, Varint::Sleb128), Ok(vec![0x40]));
+		assert!(encode(&bits_of(-1i128 as u128, 8), true, Varint::Uleb128).is_err());
+		assert_eq!(decode("E58E26", Varint::Uleb128).map(value_of), Ok(624485));
+		assert_eq!(decode("C0BB78", Varint::Sleb128).map(value_of), Ok(-123456));
+		assert_eq!(decode("40", Varint::Sleb128).map(value_of), Ok(-64));
+		assert!(decode("E58E", Varint::Uleb128).is_err());
+		assert!(decode("0000", Varint::Uleb128).is_err());
+	}
+
+	#[test]
+	fn protobuf_tests() {
+		assert_eq!(encode(&bits_of(150, 16), false, Varint::Protobuf(false)), Ok(vec![0x96, 0x01]));
+		assert_eq!(encode(&bits_of(-1i128 as u128, 8), true, Varint::Protobuf(false)), Ok(vec![0xFF, 0xFF, 0xFF, 0xFF, 0xFF, 0xFF, 0xFF, 0xFF, 0xFF, 0x01]));
+		assert_eq!(encode(&bits_of(-1i128 as u128, 8), true, Varint::Protobuf(true)), Ok(vec![0x01]));
+		assert_eq!(encode(&bits_of(-64i128 as u128, 8), true, Varint::Protobuf(true)), Ok(vec![0x7F]));
+		assert_eq!(encode(&bits_of(64, 8), false, Varint::Protobuf(true)), Ok(vec![0x80, 0x01]));
+		assert_eq!(decode("FFFFFFFFFFFFFFFFFF01", Varint::Protobuf(false)).map(value_of), Ok(u64::MAX as i128));
+		assert_eq!(decode("03", Varint::Protobuf(true)).map(value_of), Ok(-2));
+		assert!(decode("FFFFFFFFFFFFFFFFFF7F", Varint::Protobuf(false)).is_err());
+	}
+
+	#[test]
+	fn quic_tests() {
+		assert_eq!(encode(&bits_of(37, 8), false, Varint::Quic), Ok(vec![0x25]));
+		assert_eq!(encode(&bits_of(15293, 16), false, Varint::Quic), Ok(vec![0x7B, 0xBD]));
+		assert_eq!(encode(&bits_of(494878333, 32), false, Varint::Quic), Ok(vec![0x9D, 0x7F, 0x3E, 0x7D]));
+		assert_eq!(encode(&bits_of(151288809941952652, 64), false, Varint::Quic), Ok(vec![0xC2, 0x19, 0x7C, 0x5E, 0xFF, 0x14, 0xE8, 0x8C]));
+		assert_eq!(encode(&bits_of(1 << 62, 64), false, Varint::Quic), Err("Number too large for QUIC varint".to_string()));
+		assert_eq!(decode("7BBD", Varint::Quic).map(value_of), Ok(15293));
+		assert_eq!(decode("C2197C5EFF14E88C", Varint::Quic).map(value_of), Ok(151288809941952652));
+		assert!(decode("7B", Varint::Quic).is_err());
+	}
+
+	#[test]
+	fn vlq_tests() {
+		assert_eq!(encode(&bits_of(0x3FFF, 16), false, Varint::Vlq), Ok(vec![0xFF, 0x7F]));
+		assert_eq!(encode(&bits_of(0x2000, 16), false, Varint::Vlq), Ok(vec![0xC0, 0x00]));
+		assert_eq!(decode("C000", Varint::Vlq).map(value_of), Ok(0x2000));
+		assert_eq!(encode(&bits_of(128, 16), false, Varint::Git), Ok(vec![0x80, 0x00]));
+		assert_eq!(encode(&bits_of(16511, 16), false, Varint::Git), Ok(vec![0xFF, 0x7F]));
+		assert_eq!(decode("8000", Varint::Git).map(value_of), Ok(128));
+		assert_eq!(decode("FF7F", Varint::Git).map(value_of), Ok(16511));
+	}
+}